@@ -0,0 +1,340 @@
+//! Refractive surgery ablation depth and residual stromal bed screening.
+//!
+//! ## Overview
+//! - Estimates the excimer ablation depth of a LASIK/PRK correction with the
+//!   Munnerlyn formula.
+//! - Computes the residual stromal bed (RSB) and percent tissue altered (PTA)
+//!   from pachymetry and flap thickness, and flags unsafe configurations.
+//!
+//! ## Formula
+//! ```text
+//! Depth (µm) = S² × |D| / 3
+//! RSB   (µm) = Pachymetry − Flap − Depth
+//! PTA   (%)  = (Flap + Depth) / Pachymetry × 100
+//! ```
+//!
+//! Where:
+//! - **S**: Effective ablation diameter (mm)
+//! - **D**: Correction along the principal meridian of greatest power (D)
+//!
+//! ## Clinical Notes
+//! - Toric corrections are treated along the meridian of greatest absolute power,
+//!   i.e. the steepest meridian of a myopic correction.
+//! - With a transition zone, the blend ring is counted as half optical, so the
+//!   effective diameter is the mean of the optical and total ablation zones.
+//!   This is conservative (deeper) compared with most laser profiles.
+//! - For PRK, pass the epithelial thickness as the flap thickness.
+
+use crate::*;
+
+/// Treatment zone of an excimer ablation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AblationZone {
+    /// Diameter of the fully corrected optical zone (mm).
+    /// Example: 6.5 mm optical zone → `optical_zone_mm = 6.5`.
+    pub optical_zone_mm: f64,
+
+    /// Total ablation diameter including the transition zone (mm), if any.
+    /// Example: 6.5 mm OZ blended out to 8.0 mm → `transition_zone_mm = Some(8.0)`.
+    pub transition_zone_mm: Option<f64>,
+}
+
+impl AblationZone {
+    /// Returns the diameter used in the Munnerlyn formula (mm).
+    pub fn effective_diameter_mm(&self) -> f64 {
+        match self.transition_zone_mm {
+            Some(tz) => (self.optical_zone_mm + tz.max(self.optical_zone_mm)) / 2.0,
+            None => self.optical_zone_mm,
+        }
+    }
+}
+
+/// Corneal measurements used to screen a refractive surgery candidate.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct CornealTissue {
+    /// Central corneal thickness (µm).
+    pub central_pachymetry_um: f64,
+
+    /// LASIK flap thickness, or epithelial thickness for PRK (µm).
+    pub flap_thickness_um: f64,
+}
+
+/// Safety limits applied when screening a refractive surgery candidate.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SafetyThresholds {
+    /// Minimum residual stromal bed (µm). Default: 250 µm.
+    pub min_residual_stromal_bed_um: f64,
+
+    /// Maximum percent tissue altered (%). Default: 40 %.
+    pub max_percent_tissue_altered: f64,
+
+    /// Minimum total postoperative corneal thickness (µm). Default: 400 µm.
+    pub min_postoperative_thickness_um: f64,
+}
+
+impl Default for SafetyThresholds {
+    fn default() -> Self {
+        Self {
+            min_residual_stromal_bed_um: 250.0,
+            max_percent_tissue_altered: 40.0,
+            min_postoperative_thickness_um: 400.0,
+        }
+    }
+}
+
+/// A safety limit exceeded by a planned ablation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum SafetyFlag {
+    /// The residual stromal bed is thinner than the minimum.
+    ResidualStromalBed,
+
+    /// The percent tissue altered exceeds the maximum.
+    PercentTissueAltered,
+
+    /// The postoperative corneal thickness is thinner than the minimum.
+    PostoperativeThickness,
+}
+
+/// Result of a refractive surgery screening.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AblationScreening {
+    /// Estimated maximum ablation depth (µm).
+    pub ablation_depth_um: f64,
+
+    /// Residual stromal bed under the ablation (µm).
+    pub residual_stromal_bed_um: f64,
+
+    /// Percent tissue altered (%).
+    pub percent_tissue_altered: f64,
+
+    /// Postoperative central corneal thickness (µm).
+    pub postoperative_thickness_um: f64,
+
+    /// Safety limits that were exceeded; empty when all limits are met.
+    pub flags: Vec<SafetyFlag>,
+}
+
+impl AblationScreening {
+    /// Returns `true` if no safety limit was exceeded.
+    pub fn is_within_limits(&self) -> bool {
+        self.flags.is_empty()
+    }
+}
+
+/// Estimate the maximum ablation depth (µm) of a correction using the Munnerlyn formula.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // −4.00 DS over a 6.0 mm optical zone: 6² × 4 / 3 = 48 µm
+/// let rx = SpheroCyl { sphere: -4.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let zone = AblationZone { optical_zone_mm: 6.0, transition_zone_mm: None };
+/// assert_abs_diff_eq!(munnerlyn_ablation_depth(rx, zone), 48.0, epsilon = 1e-9);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if the optical zone is not positive.
+pub fn munnerlyn_ablation_depth(correction: SpheroCyl, zone: AblationZone) -> f64 {
+    debug_assert!(
+        zone.optical_zone_mm > 0.0,
+        "optical_zone_mm must be positive"
+    );
+
+    // Principal meridians are the sphere and sphere + cylinder
+    let meridian_power = correction
+        .sphere
+        .abs()
+        .max((correction.sphere + correction.cylinder).abs());
+    let diameter = zone.effective_diameter_mm();
+
+    diameter * diameter * meridian_power / 3.0
+}
+
+/// Screen a LASIK/PRK correction against corneal thickness safety limits.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let rx = SpheroCyl { sphere: -6.0, cylinder: -1.0, axis_deg: 180.0 };
+/// let zone = AblationZone { optical_zone_mm: 6.5, transition_zone_mm: None };
+/// let tissue = CornealTissue { central_pachymetry_um: 540.0, flap_thickness_um: 110.0 };
+///
+/// let screening = screen_refractive_surgery(rx, zone, tissue, SafetyThresholds::default());
+/// // 6.5² × 7 / 3 ≈ 98.6 µm → RSB ≈ 331 µm, PTA ≈ 38.6 %
+/// assert!(screening.is_within_limits());
+/// ```
+pub fn screen_refractive_surgery(
+    correction: SpheroCyl,
+    zone: AblationZone,
+    tissue: CornealTissue,
+    thresholds: SafetyThresholds,
+) -> AblationScreening {
+    debug_assert!(
+        tissue.central_pachymetry_um > 0.0,
+        "central_pachymetry_um must be positive"
+    );
+
+    let ablation_depth_um = munnerlyn_ablation_depth(correction, zone);
+    let residual_stromal_bed_um =
+        tissue.central_pachymetry_um - tissue.flap_thickness_um - ablation_depth_um;
+    let percent_tissue_altered =
+        (tissue.flap_thickness_um + ablation_depth_um) / tissue.central_pachymetry_um * 100.0;
+    let postoperative_thickness_um = tissue.central_pachymetry_um - ablation_depth_um;
+
+    let mut flags = Vec::new();
+    if residual_stromal_bed_um < thresholds.min_residual_stromal_bed_um {
+        flags.push(SafetyFlag::ResidualStromalBed);
+    }
+    if percent_tissue_altered > thresholds.max_percent_tissue_altered {
+        flags.push(SafetyFlag::PercentTissueAltered);
+    }
+    if postoperative_thickness_um < thresholds.min_postoperative_thickness_um {
+        flags.push(SafetyFlag::PostoperativeThickness);
+    }
+
+    AblationScreening {
+        ablation_depth_um,
+        residual_stromal_bed_um,
+        percent_tissue_altered,
+        postoperative_thickness_um,
+        flags,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    fn zone(oz: f64) -> AblationZone {
+        AblationZone {
+            optical_zone_mm: oz,
+            transition_zone_mm: None,
+        }
+    }
+
+    #[test]
+    fn toric_correction_uses_steepest_meridian() {
+        // −3.00 / −1.50 × 180 → −4.50 D in the 90° meridian
+        let rx = SpheroCyl {
+            sphere: -3.0,
+            cylinder: -1.5,
+            axis_deg: 180.0,
+        };
+        assert_abs_diff_eq!(munnerlyn_ablation_depth(rx, zone(6.0)), 54.0, epsilon = EPS);
+
+        // Same lens in plus-cylinder form gives the same depth
+        assert_abs_diff_eq!(
+            munnerlyn_ablation_depth(rx.transpose(), zone(6.0)),
+            54.0,
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn hyperopic_correction_uses_absolute_power() {
+        let rx = SpheroCyl {
+            sphere: 2.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        assert_abs_diff_eq!(munnerlyn_ablation_depth(rx, zone(6.0)), 24.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn transition_zone_widens_effective_diameter() {
+        let rx = SpheroCyl {
+            sphere: -3.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let z = AblationZone {
+            optical_zone_mm: 6.0,
+            transition_zone_mm: Some(8.0),
+        };
+        assert_abs_diff_eq!(z.effective_diameter_mm(), 7.0, epsilon = EPS);
+        assert_abs_diff_eq!(munnerlyn_ablation_depth(rx, z), 49.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn screening_computes_rsb_and_pta() {
+        let rx = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let tissue = CornealTissue {
+            central_pachymetry_um: 500.0,
+            flap_thickness_um: 100.0,
+        };
+        let s = screen_refractive_surgery(rx, zone(6.0), tissue, SafetyThresholds::default());
+        assert_abs_diff_eq!(s.ablation_depth_um, 48.0, epsilon = EPS);
+        assert_abs_diff_eq!(s.residual_stromal_bed_um, 352.0, epsilon = EPS);
+        assert_abs_diff_eq!(s.percent_tissue_altered, 29.6, epsilon = EPS);
+        assert_abs_diff_eq!(s.postoperative_thickness_um, 452.0, epsilon = EPS);
+        assert!(s.is_within_limits());
+    }
+
+    #[test]
+    fn thin_cornea_is_flagged() {
+        let rx = SpheroCyl {
+            sphere: -8.0,
+            cylinder: -2.0,
+            axis_deg: 90.0,
+        };
+        let tissue = CornealTissue {
+            central_pachymetry_um: 480.0,
+            flap_thickness_um: 120.0,
+        };
+        let s = screen_refractive_surgery(rx, zone(6.5), tissue, SafetyThresholds::default());
+        assert_eq!(
+            s.flags,
+            vec![
+                SafetyFlag::ResidualStromalBed,
+                SafetyFlag::PercentTissueAltered,
+                SafetyFlag::PostoperativeThickness,
+            ]
+        );
+    }
+
+    #[test]
+    fn thresholds_are_configurable() {
+        let rx = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let tissue = CornealTissue {
+            central_pachymetry_um: 500.0,
+            flap_thickness_um: 100.0,
+        };
+        let strict = SafetyThresholds {
+            min_residual_stromal_bed_um: 360.0,
+            ..SafetyThresholds::default()
+        };
+        let s = screen_refractive_surgery(rx, zone(6.0), tissue, strict);
+        assert_eq!(s.flags, vec![SafetyFlag::ResidualStromalBed]);
+    }
+}
@@ -0,0 +1,262 @@
+//! Amplitude of accommodation and tentative near add.
+//!
+//! ## Overview
+//! - Estimates the expected amplitude of accommodation from age using
+//!   Hofstetter's formulas.
+//! - Computes a tentative add from the working distance, holding part of the
+//!   amplitude in reserve.
+//! - Computes the range of clear vision through an add and the near Rx.
+//!
+//! ## Formulas
+//! ```text
+//! Hofstetter minimum = 15.0 − 0.25 × age
+//! Hofstetter mean    = 18.5 − 0.30 × age
+//! Hofstetter maximum = 25.0 − 0.40 × age
+//!
+//! Add = 1 / working distance (m) − usable fraction × amplitude
+//! Far point  = 1 / Add
+//! Near point = 1 / (Add + amplitude)
+//! ```
+//!
+//! ## Clinical Notes
+//! - Amplitudes and adds are in **diopters (D)**, distances in **metres (m)**.
+//! - Points are measured from the spectacle plane, assuming the distance Rx is
+//!   fully corrected.
+
+use crate::*;
+
+/// Expected amplitude of accommodation for an age, after Hofstetter.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct HofstetterAmplitude {
+    /// Minimum expected amplitude (D).
+    pub minimum: f64,
+    /// Mean expected amplitude (D).
+    pub mean: f64,
+    /// Maximum expected amplitude (D).
+    pub maximum: f64,
+}
+
+/// How much of the amplitude of accommodation a patient can comfortably sustain.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum AccommodationReserve {
+    /// Use one half of the amplitude, keeping one half in reserve.
+    OneHalf,
+    /// Use two thirds of the amplitude, keeping one third in reserve.
+    TwoThirds,
+}
+
+impl AccommodationReserve {
+    /// Returns the fraction of the amplitude available for sustained near work.
+    pub fn usable_fraction(&self) -> f64 {
+        match self {
+            AccommodationReserve::OneHalf => 0.5,
+            AccommodationReserve::TwoThirds => 2.0 / 3.0,
+        }
+    }
+}
+
+/// Range of clear vision through a near add.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RangeOfClearVision {
+    /// Nearest point seen clearly, using the full amplitude (m).
+    pub near_point_m: f64,
+
+    /// Farthest point seen clearly with accommodation relaxed (m).
+    /// `None` means optical infinity (no add).
+    pub far_point_m: Option<f64>,
+}
+
+impl RangeOfClearVision {
+    /// Returns the depth of the range in metres, or `None` if it extends to infinity.
+    pub fn depth_m(&self) -> Option<f64> {
+        self.far_point_m.map(|far| far - self.near_point_m)
+    }
+}
+
+/// Compute Hofstetter's minimum, mean and maximum amplitude of accommodation for an age.
+///
+/// Values are clamped at zero for advanced ages.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// let amp = hofstetter_amplitude(40.0);
+/// assert_abs_diff_eq!(amp.minimum, 5.0, epsilon = 1e-12);
+/// assert_abs_diff_eq!(amp.mean, 6.5, epsilon = 1e-12);
+/// assert_abs_diff_eq!(amp.maximum, 9.0, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `age_years` is negative.
+pub fn hofstetter_amplitude(age_years: f64) -> HofstetterAmplitude {
+    debug_assert!(age_years >= 0.0, "age_years must be non-negative");
+
+    HofstetterAmplitude {
+        minimum: (15.0 - 0.25 * age_years).max(0.0),
+        mean: (18.5 - 0.3 * age_years).max(0.0),
+        maximum: (25.0 - 0.4 * age_years).max(0.0),
+    }
+}
+
+/// Compute a tentative near add from the amplitude of accommodation and working distance.
+///
+/// The add supplies whatever part of the accommodative demand the patient cannot
+/// sustain while keeping the chosen reserve. Returns `0.0` if no add is needed.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // 40 cm demand = 2.50 D; half of a 3.00 D amplitude = 1.50 D → +1.00 add
+/// let add = tentative_add(3.0, 0.40, AccommodationReserve::OneHalf);
+/// assert_abs_diff_eq!(add, 1.0, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `working_distance_m` is not positive.
+pub fn tentative_add(
+    amplitude_d: f64,
+    working_distance_m: f64,
+    reserve: AccommodationReserve,
+) -> f64 {
+    debug_assert!(
+        working_distance_m > 0.0,
+        "working_distance_m must be positive"
+    );
+
+    let demand = 1.0 / working_distance_m;
+    (demand - amplitude_d * reserve.usable_fraction()).max(0.0)
+}
+
+/// Compute the range of clear vision through an add for a given amplitude.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // +2.00 add with 1.00 D amplitude: clear from 33 cm to 50 cm
+/// let range = range_of_clear_vision(2.0, 1.0);
+/// assert_abs_diff_eq!(range.near_point_m, 1.0 / 3.0, epsilon = 1e-12);
+/// assert_abs_diff_eq!(range.far_point_m.unwrap(), 0.5, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `add_d` or `amplitude_d` is negative.
+pub fn range_of_clear_vision(add_d: f64, amplitude_d: f64) -> RangeOfClearVision {
+    debug_assert!(add_d >= 0.0, "add_d must be non-negative");
+    debug_assert!(amplitude_d >= 0.0, "amplitude_d must be non-negative");
+
+    let far_point_m = if add_d > 0.0 { Some(1.0 / add_d) } else { None };
+    RangeOfClearVision {
+        near_point_m: 1.0 / (add_d + amplitude_d),
+        far_point_m,
+    }
+}
+
+/// Combine a distance Rx with a near add to produce the near Rx.
+///
+/// The add is spherical, so only the sphere changes.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// let distance = SpheroCyl { sphere: -1.25, cylinder: -0.50, axis_deg: 90.0 };
+/// let near = near_rx(distance, 2.0);
+/// assert_eq!(near.sphere, 0.75);
+/// assert_eq!(near.cylinder, -0.50);
+/// ```
+pub fn near_rx(distance: SpheroCyl, add_d: f64) -> SpheroCyl {
+    SpheroCyl {
+        sphere: distance.sphere + add_d,
+        cylinder: distance.cylinder,
+        axis_deg: distance.axis_deg,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-12;
+
+    #[test]
+    fn hofstetter_clamps_at_zero() {
+        let amp = hofstetter_amplitude(70.0);
+        assert_abs_diff_eq!(amp.minimum, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(amp.mean, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(amp.maximum, 0.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn hofstetter_ordering() {
+        for age in [10.0, 25.0, 45.0, 55.0] {
+            let amp = hofstetter_amplitude(age);
+            assert!(amp.minimum <= amp.mean && amp.mean <= amp.maximum);
+        }
+    }
+
+    #[test]
+    fn two_thirds_rule_gives_lower_add() {
+        let half = tentative_add(2.4, 0.4, AccommodationReserve::OneHalf);
+        let two_thirds = tentative_add(2.4, 0.4, AccommodationReserve::TwoThirds);
+        assert_abs_diff_eq!(half, 1.3, epsilon = EPS);
+        assert_abs_diff_eq!(two_thirds, 0.9, epsilon = EPS);
+    }
+
+    #[test]
+    fn young_patient_needs_no_add() {
+        let amp = hofstetter_amplitude(20.0).minimum;
+        assert_abs_diff_eq!(
+            tentative_add(amp, 0.33, AccommodationReserve::OneHalf),
+            0.0,
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn range_without_add_extends_to_infinity() {
+        let range = range_of_clear_vision(0.0, 4.0);
+        assert_abs_diff_eq!(range.near_point_m, 0.25, epsilon = EPS);
+        assert!(range.far_point_m.is_none());
+        assert!(range.depth_m().is_none());
+    }
+
+    #[test]
+    fn add_and_amplitude_range_depth() {
+        let range = range_of_clear_vision(2.5, 0.0);
+        assert_abs_diff_eq!(range.near_point_m, 0.4, epsilon = EPS);
+        assert_abs_diff_eq!(range.depth_m().unwrap(), 0.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn near_rx_from_age_and_distance() {
+        let amp = hofstetter_amplitude(52.0).minimum; // 2.00 D
+        let add = tentative_add(amp, 0.4, AccommodationReserve::OneHalf); // 1.50 D
+        let near = near_rx(
+            SpheroCyl {
+                sphere: 1.0,
+                cylinder: -0.75,
+                axis_deg: 170.0,
+            },
+            add,
+        );
+        assert_abs_diff_eq!(near.sphere, 2.5, epsilon = EPS);
+        assert_abs_diff_eq!(near.cylinder, -0.75, epsilon = EPS);
+        assert_abs_diff_eq!(near.axis_deg, 170.0, epsilon = EPS);
+    }
+}
@@ -0,0 +1,382 @@
+//! Astigmatism vector analysis of surgical outcomes (Alpins method).
+//!
+//! ## Overview
+//! Compares the astigmatism change a procedure aimed for with the change it
+//! achieved. All vectors are handled in double-angle space, where astigmatism
+//! 180° apart coincides and ordinary vector arithmetic applies.
+//!
+//! ## Vectors
+//! ```text
+//! TIA = Target − Preop        (target induced astigmatism)
+//! SIA = Postop − Preop        (surgically induced astigmatism)
+//! DV  = Target − Postop       (difference vector, = TIA − SIA)
+//! ```
+//!
+//! ## Indices
+//! - **Correction index** CI = |SIA| / |TIA| (1 = full, >1 = overcorrection).
+//! - **Index of success** IOS = |DV| / |TIA| (0 = ideal).
+//! - **Magnitude of error** ME = |SIA| − |TIA|.
+//! - **Angle of error** AE = ∠SIA − ∠TIA, in (−90°, 90°], positive = counter-clockwise.
+//! - **Flattening effect** FE = |SIA|·cos(2·AE), and **flattening index** FI = FE / |TIA|.
+//!
+//! Ratios are `NaN` when the TIA is zero. Refractive astigmatism should be
+//! vertexed to the corneal plane before comparing it with keratometry.
+
+use crate::*;
+
+/// Alpins vector analysis of a single eye.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AlpinsAnalysis {
+    /// Target induced astigmatism (TIA).
+    pub target_induced: Astigmatism,
+
+    /// Surgically induced astigmatism (SIA).
+    pub surgically_induced: Astigmatism,
+
+    /// Difference vector (DV): the further change needed to reach the target.
+    pub difference_vector: Astigmatism,
+
+    /// Correction index, |SIA| / |TIA|.
+    pub correction_index: f64,
+
+    /// Index of success, |DV| / |TIA|.
+    pub index_of_success: f64,
+
+    /// Magnitude of error, |SIA| − |TIA| (D).
+    pub magnitude_of_error: f64,
+
+    /// Angle of error in degrees, range (−90, 90].
+    /// Positive when the SIA is counter-clockwise of the TIA.
+    pub angle_of_error_deg: f64,
+
+    /// Flattening effect, |SIA|·cos(2·AE) (D).
+    pub flattening_effect: f64,
+
+    /// Flattening index, FE / |TIA|.
+    pub flattening_index: f64,
+}
+
+/// Centroid and spread of a set of astigmatism vectors in double-angle space.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AstigmatismCentroid {
+    /// Vector mean, converted back to a magnitude and meridian.
+    pub centroid: Astigmatism,
+
+    /// Sample standard deviation of the double-angle x component (D).
+    pub sd_x: f64,
+
+    /// Sample standard deviation of the double-angle y component (D).
+    pub sd_y: f64,
+
+    /// Arithmetic mean of the magnitudes, ignoring direction (D).
+    pub mean_magnitude: f64,
+
+    /// Sample standard deviation of the magnitudes (D).
+    pub sd_magnitude: f64,
+}
+
+/// Aggregate Alpins statistics over a cohort.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AlpinsCohort {
+    /// Number of eyes in the cohort.
+    pub count: usize,
+
+    /// Centroid of the target induced astigmatism.
+    pub target_induced: AstigmatismCentroid,
+
+    /// Centroid of the surgically induced astigmatism.
+    pub surgically_induced: AstigmatismCentroid,
+
+    /// Centroid of the difference vector.
+    pub difference_vector: AstigmatismCentroid,
+
+    /// Mean correction index over eyes with a non-zero TIA.
+    pub mean_correction_index: f64,
+
+    /// Sample standard deviation of the correction index.
+    pub sd_correction_index: f64,
+
+    /// Mean angle of error (degrees) over eyes with a non-zero TIA.
+    pub mean_angle_of_error_deg: f64,
+
+    /// Sample standard deviation of the angle of error (degrees).
+    pub sd_angle_of_error_deg: f64,
+}
+
+/// Returns `a − b`, computed in double-angle space.
+fn subtract(a: Astigmatism, b: Astigmatism) -> Astigmatism {
+    let (ax, ay) = a.double_angle();
+    let (bx, by) = b.double_angle();
+    Astigmatism::from_double_angle(ax - bx, ay - by)
+}
+
+/// Perform an Alpins vector analysis of one eye.
+///
+/// Each argument accepts an [`Astigmatism`], a refraction as [`SpheroCyl`] or a
+/// [`Keratometry`] reading.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let preop = Keratometry { flat_k: 43.0, steep_k: 45.0, steep_axis_deg: 90.0 };
+/// let target = Astigmatism::new(0.0, 0.0);
+/// let postop = Keratometry { flat_k: 43.5, steep_k: 44.0, steep_axis_deg: 90.0 };
+///
+/// let a = alpins_analysis(preop, target, postop);
+/// assert_abs_diff_eq!(a.target_induced.magnitude, 2.0, epsilon = 1e-9);
+/// assert_abs_diff_eq!(a.surgically_induced.magnitude, 1.5, epsilon = 1e-9);
+/// assert_abs_diff_eq!(a.correction_index, 0.75, epsilon = 1e-9);
+/// assert_abs_diff_eq!(a.angle_of_error_deg, 0.0, epsilon = 1e-9);
+/// ```
+pub fn alpins_analysis(
+    preop: impl Into<Astigmatism>,
+    target: impl Into<Astigmatism>,
+    postop: impl Into<Astigmatism>,
+) -> AlpinsAnalysis {
+    let preop = preop.into();
+    let target = target.into();
+    let postop = postop.into();
+
+    let tia = subtract(target, preop);
+    let sia = subtract(postop, preop);
+    let dv = subtract(target, postop);
+
+    let mut angle_of_error_deg = sia.axis_deg - tia.axis_deg;
+    if angle_of_error_deg > 90.0 {
+        angle_of_error_deg -= 180.0;
+    } else if angle_of_error_deg <= -90.0 {
+        angle_of_error_deg += 180.0;
+    }
+
+    let flattening_effect = sia.magnitude * (2.0 * angle_of_error_deg).to_radians().cos();
+    let ratio = |value: f64| {
+        if tia.magnitude == 0.0 {
+            f64::NAN
+        } else {
+            value / tia.magnitude
+        }
+    };
+
+    AlpinsAnalysis {
+        target_induced: tia,
+        surgically_induced: sia,
+        difference_vector: dv,
+        correction_index: ratio(sia.magnitude),
+        index_of_success: ratio(dv.magnitude),
+        magnitude_of_error: sia.magnitude - tia.magnitude,
+        angle_of_error_deg,
+        flattening_effect,
+        flattening_index: ratio(flattening_effect),
+    }
+}
+
+/// Returns the mean and sample standard deviation, or `(0, 0)` for no values.
+fn mean_sd(values: &[f64]) -> (f64, f64) {
+    if values.is_empty() {
+        return (0.0, 0.0);
+    }
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let var = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, var.sqrt())
+}
+
+/// Compute the double-angle centroid and spread of a set of vectors.
+///
+/// Returns `None` if `vectors` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // Equal astigmatism 90° apart cancels out in the centroid
+/// let c = astigmatism_centroid(&[Astigmatism::new(1.0, 90.0), Astigmatism::new(1.0, 180.0)]).unwrap();
+/// assert_abs_diff_eq!(c.centroid.magnitude, 0.0, epsilon = 1e-9);
+/// assert_abs_diff_eq!(c.mean_magnitude, 1.0, epsilon = 1e-9);
+/// ```
+pub fn astigmatism_centroid(vectors: &[Astigmatism]) -> Option<AstigmatismCentroid> {
+    if vectors.is_empty() {
+        return None;
+    }
+
+    let (xs, ys): (Vec<f64>, Vec<f64>) = vectors.iter().map(|v| v.double_angle()).unzip();
+    let magnitudes: Vec<f64> = vectors.iter().map(|v| v.magnitude).collect();
+
+    let (mean_x, sd_x) = mean_sd(&xs);
+    let (mean_y, sd_y) = mean_sd(&ys);
+    let (mean_magnitude, sd_magnitude) = mean_sd(&magnitudes);
+
+    Some(AstigmatismCentroid {
+        centroid: Astigmatism::from_double_angle(mean_x, mean_y),
+        sd_x,
+        sd_y,
+        mean_magnitude,
+        sd_magnitude,
+    })
+}
+
+/// Aggregate Alpins analyses over a cohort.
+///
+/// Vectors are averaged in double-angle space. Eyes with a zero TIA contribute
+/// to the vector centroids but not to the correction index or angle of error.
+///
+/// Returns `None` if `cases` is empty.
+pub fn alpins_cohort(cases: &[AlpinsAnalysis]) -> Option<AlpinsCohort> {
+    let tia: Vec<Astigmatism> = cases.iter().map(|c| c.target_induced).collect();
+    let sia: Vec<Astigmatism> = cases.iter().map(|c| c.surgically_induced).collect();
+    let dv: Vec<Astigmatism> = cases.iter().map(|c| c.difference_vector).collect();
+
+    let ci: Vec<f64> = cases
+        .iter()
+        .map(|c| c.correction_index)
+        .filter(|v| v.is_finite())
+        .collect();
+    let ae: Vec<f64> = cases
+        .iter()
+        .filter(|c| c.target_induced.magnitude > 0.0)
+        .map(|c| c.angle_of_error_deg)
+        .collect();
+
+    let (mean_correction_index, sd_correction_index) = mean_sd(&ci);
+    let (mean_angle_of_error_deg, sd_angle_of_error_deg) = mean_sd(&ae);
+
+    Some(AlpinsCohort {
+        count: cases.len(),
+        target_induced: astigmatism_centroid(&tia)?,
+        surgically_induced: astigmatism_centroid(&sia)?,
+        difference_vector: astigmatism_centroid(&dv)?,
+        mean_correction_index,
+        sd_correction_index,
+        mean_angle_of_error_deg,
+        sd_angle_of_error_deg,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn perfect_outcome_has_unit_ci_and_zero_dv() {
+        let preop = Astigmatism::new(1.5, 80.0);
+        let target = Astigmatism::new(0.0, 0.0);
+        let a = alpins_analysis(preop, target, target);
+
+        // TIA points 90° away from the preop steep meridian
+        assert_abs_diff_eq!(a.target_induced.magnitude, 1.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.target_induced.axis_deg, 170.0, epsilon = EPS);
+        assert_abs_diff_eq!(a.correction_index, 1.0, epsilon = EPS);
+        assert_abs_diff_eq!(a.index_of_success, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(a.difference_vector.magnitude, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(a.magnitude_of_error, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(a.flattening_index, 1.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn overcorrection_flips_meridian() {
+        let preop = Astigmatism::new(1.0, 90.0);
+        let target = Astigmatism::new(0.0, 0.0);
+        let postop = Astigmatism::new(0.5, 180.0);
+        let a = alpins_analysis(preop, target, postop);
+
+        assert_abs_diff_eq!(a.correction_index, 1.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.magnitude_of_error, 0.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.difference_vector.magnitude, 0.5, epsilon = EPS);
+        // DV must undo the overcorrection: steep at 90
+        assert_abs_diff_eq!(a.difference_vector.axis_deg, 90.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn angle_of_error_sign_is_counter_clockwise_positive() {
+        // Treatment rotated: SIA 1.0 @ 10° instead of 1.0 @ 0°
+        let preop = Astigmatism::new(1.0, 90.0);
+        let target = Astigmatism::new(0.0, 0.0);
+        let (tx, ty) = preop.double_angle();
+        let (sx, sy) = Astigmatism::new(1.0, 10.0).double_angle();
+        let postop = Astigmatism::from_double_angle(tx + sx, ty + sy);
+
+        let a = alpins_analysis(preop, target, postop);
+        assert_abs_diff_eq!(a.angle_of_error_deg, 10.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(a.correction_index, 1.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            a.flattening_effect,
+            20f64.to_radians().cos(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn accepts_refractions_and_keratometry() {
+        let preop = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -1.0,
+            axis_deg: 180.0,
+        };
+        let postop = Keratometry {
+            flat_k: 43.0,
+            steep_k: 43.25,
+            steep_axis_deg: 90.0,
+        };
+        let a = alpins_analysis(preop, Astigmatism::new(0.0, 0.0), postop);
+        assert_abs_diff_eq!(a.correction_index, 0.75, epsilon = EPS);
+    }
+
+    #[test]
+    fn zero_tia_gives_nan_ratios() {
+        let preop = Astigmatism::new(0.5, 90.0);
+        let a = alpins_analysis(preop, preop, Astigmatism::new(0.75, 90.0));
+        assert!(a.correction_index.is_nan());
+        assert!(a.index_of_success.is_nan());
+        assert_abs_diff_eq!(a.surgically_induced.magnitude, 0.25, epsilon = EPS);
+    }
+
+    #[test]
+    fn cohort_aggregates_centroids_and_indices() {
+        let target = Astigmatism::new(0.0, 0.0);
+        let cases = [
+            alpins_analysis(
+                Astigmatism::new(1.0, 90.0),
+                target,
+                Astigmatism::new(0.25, 90.0),
+            ),
+            alpins_analysis(
+                Astigmatism::new(2.0, 90.0),
+                target,
+                Astigmatism::new(0.25, 180.0),
+            ),
+        ];
+        let cohort = alpins_cohort(&cases).unwrap();
+
+        assert_eq!(cohort.count, 2);
+        assert_abs_diff_eq!(cohort.target_induced.centroid.magnitude, 1.5, epsilon = EPS);
+        assert_abs_diff_eq!(cohort.target_induced.centroid.axis_deg, 0.0, epsilon = EPS);
+        // CI: 0.75 and 1.125
+        assert_abs_diff_eq!(cohort.mean_correction_index, 0.9375, epsilon = EPS);
+        assert_abs_diff_eq!(
+            cohort.sd_correction_index,
+            (2.0 * 0.1875f64.powi(2)).sqrt(),
+            epsilon = EPS
+        );
+        assert!(alpins_cohort(&[]).is_none());
+    }
+}
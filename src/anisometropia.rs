@@ -0,0 +1,332 @@
+//! Anisometropia and aniseikonia risk assessment.
+//!
+//! ## Overview
+//! [`assess_anisometropia`] compares the right and left lens of a pair and
+//! estimates the consequences of the difference: the image size difference
+//! (aniseikonia) with spectacles and with contact lenses, and the vertical
+//! prism imbalance when reading below the optical centers. It grades the
+//! anisometropia and suggests ways to manage it.
+//!
+//! ## Formulas
+//! ```text
+//! Meridional power:   P(φ) = M + J0·cos 2φ + J45·sin 2φ
+//! Worst meridian:     max |P_OD(φ) − P_OS(φ)| = |ΔM| + √(ΔJ0² + ΔJ45²)
+//! Power magnification: SM = 1 / (1 − h·F)
+//!   spectacles:       h = vertex + 3 mm, F = spectacle power
+//!   contact lenses:   h = 3 mm,          F = power at the cornea
+//! Aniseikonia:        (SM_OD / SM_OS − 1) · 100 %
+//! Vertical imbalance: P_OD(90°)·d − P_OS(90°)·d   (Prentice, d in cm)
+//! ```
+//!
+//! ## Conventions
+//! - Aniseikonia is positive when the right image is larger and ignores the
+//!   lens shape factor. It assumes refractive anisometropia; with axial
+//!   anisometropia spectacles usually give the smaller image difference
+//!   (Knapp's law).
+//! - Vertical imbalance is OD − OS, positive when the right lens has more
+//!   base-up prism.
+
+use crate::*;
+
+/// Depth of the entrance pupil behind the cornea (mm).
+const ENTRANCE_PUPIL_DEPTH_MM: f64 = 3.0;
+
+/// Aniseikonia above which the size difference is usually noticed (%).
+pub const ANISEIKONIA_NOTICEABLE_PERCENT: f64 = 1.0;
+
+/// Aniseikonia above which symptoms are likely (%).
+pub const ANISEIKONIA_SYMPTOMATIC_PERCENT: f64 = 3.0;
+
+/// Vertical imbalance above which a slab-off is usually considered (Δ).
+pub const SLAB_OFF_THRESHOLD: f64 = 1.5;
+
+/// Position of wear for the assessment.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AnisometropiaSetup {
+    /// Vertex distance of the spectacles (mm).
+    ///
+    /// Example: 12 mm vertex → `vertex_distance_mm = 12.0`
+    pub vertex_distance_mm: f64,
+
+    /// Distance of the reading point below the optical centers (mm).
+    ///
+    /// Example: reading 10 mm below the OCs → `reading_depth_mm = 10.0`
+    pub reading_depth_mm: f64,
+}
+
+impl Default for AnisometropiaSetup {
+    /// 12 mm vertex distance, reading 10 mm below the optical centers.
+    fn default() -> Self {
+        Self {
+            vertex_distance_mm: 12.0,
+            reading_depth_mm: 10.0,
+        }
+    }
+}
+
+/// Grade of anisometropia in the worst meridian.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum AnisometropiaSeverity {
+    /// Less than 1.00 D.
+    Negligible,
+
+    /// 1.00 D to less than 2.00 D.
+    Mild,
+
+    /// 2.00 D to less than 3.00 D.
+    Moderate,
+
+    /// 3.00 D or more.
+    High,
+}
+
+impl AnisometropiaSeverity {
+    /// Grades a meridional power difference (D).
+    pub fn from_difference(difference: f64) -> Self {
+        match difference.abs() {
+            d if d < 1.0 => AnisometropiaSeverity::Negligible,
+            d if d < 2.0 => AnisometropiaSeverity::Mild,
+            d if d < 3.0 => AnisometropiaSeverity::Moderate,
+            _ => AnisometropiaSeverity::High,
+        }
+    }
+}
+
+/// A way to reduce the effects of anisometropia.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum AnisometropiaOption {
+    /// Contact lenses: smaller image difference and no prism imbalance.
+    ContactLenses,
+
+    /// Iseikonic spectacle lenses, with base curve and thickness chosen to equalize image sizes.
+    IseikonicLenses,
+
+    /// Fit the frame as close to the eyes as possible.
+    MinimizeVertexDistance,
+
+    /// Slab-off (bicentric grinding) to cancel the vertical imbalance.
+    SlabOff,
+
+    /// Separate single-vision reading glasses centered for near.
+    SeparateReadingGlasses,
+}
+
+/// Result of an anisometropia assessment.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AnisometropiaAssessment {
+    /// Difference in spherical equivalent, OD − OS (D).
+    pub spherical_equivalent_difference: f64,
+
+    /// Largest power difference between the eyes in any meridian (D).
+    pub max_meridional_difference: f64,
+
+    /// Meridian of the largest difference (degrees), in (0, 180].
+    pub worst_meridian_deg: f64,
+
+    /// Estimated aniseikonia with spectacles (%).
+    pub spectacle_aniseikonia_percent: f64,
+
+    /// Estimated aniseikonia with contact lenses (%).
+    pub contact_lens_aniseikonia_percent: f64,
+
+    /// Vertical prism imbalance at the reading depth, OD − OS (Δ).
+    pub vertical_imbalance_at_reading: f64,
+
+    /// Grade of the anisometropia.
+    pub severity: AnisometropiaSeverity,
+
+    /// Suggested management; empty when none is needed.
+    pub suggestions: Vec<AnisometropiaOption>,
+}
+
+/// Spectacle power magnification for a power `power` with the lens `distance_mm`
+/// in front of the entrance pupil.
+fn power_magnification(power: f64, distance_mm: f64) -> f64 {
+    1.0 / (1.0 - distance_mm / 1000.0 * power)
+}
+
+/// Assess the anisometropia of a pair of spectacle lenses.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let od = SpheroCyl { sphere: 1.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let os = SpheroCyl { sphere: 4.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let assessment = assess_anisometropia(od, os, AnisometropiaSetup::default());
+///
+/// assert_eq!(assessment.severity, AnisometropiaSeverity::High);
+/// assert_abs_diff_eq!(assessment.spectacle_aniseikonia_percent, -4.57, epsilon = 0.01);
+/// assert!(assessment.contact_lens_aniseikonia_percent.abs() < 1.0);
+/// assert_abs_diff_eq!(assessment.vertical_imbalance_at_reading, -3.0, epsilon = 1e-12);
+/// assert_eq!(assessment.suggestions[0], AnisometropiaOption::ContactLenses);
+/// assert!(assessment.suggestions.contains(&AnisometropiaOption::SlabOff));
+/// ```
+pub fn assess_anisometropia(
+    od: SpheroCyl,
+    os: SpheroCyl,
+    setup: AnisometropiaSetup,
+) -> AnisometropiaAssessment {
+    // P(φ) is sinusoidal in 2φ, so the worst meridian follows from the power vectors
+    let difference = PowerVector::from(od) - PowerVector::from(os);
+    let double_angle_deg = difference.j45.atan2(difference.j0).to_degrees();
+    let worst_meridian_deg = if difference.m >= 0.0 {
+        normalize_axis_deg(double_angle_deg / 2.0)
+    } else {
+        normalize_axis_deg(double_angle_deg / 2.0 + 90.0)
+    };
+    let od_power = od.power_at(worst_meridian_deg);
+    let os_power = os.power_at(worst_meridian_deg);
+    let max_meridional_difference = (od_power - os_power).abs();
+
+    let aniseikonia = |od_magnification: f64, os_magnification: f64| {
+        (od_magnification / os_magnification - 1.0) * 100.0
+    };
+    let spectacle_distance_mm = setup.vertex_distance_mm + ENTRANCE_PUPIL_DEPTH_MM;
+    let spectacle_aniseikonia_percent = aniseikonia(
+        power_magnification(od_power, spectacle_distance_mm),
+        power_magnification(os_power, spectacle_distance_mm),
+    );
+    let corneal = |power: f64| power / (1.0 - setup.vertex_distance_mm / 1000.0 * power);
+    let contact_lens_aniseikonia_percent = aniseikonia(
+        power_magnification(corneal(od_power), ENTRANCE_PUPIL_DEPTH_MM),
+        power_magnification(corneal(os_power), ENTRANCE_PUPIL_DEPTH_MM),
+    );
+
+    // The optical centers sit above the reading point
+    let reading = Decentration {
+        horizontal_mm: 0.0,
+        vertical_mm: setup.reading_depth_mm,
+    };
+    let vertical_imbalance_at_reading = induced_prism(Eye::OD, od, reading).vertical.signed()
+        - induced_prism(Eye::OS, os, reading).vertical.signed();
+
+    let mut suggestions = Vec::new();
+    let mut suggest = |option| {
+        if !suggestions.contains(&option) {
+            suggestions.push(option);
+        }
+    };
+    let spectacle = spectacle_aniseikonia_percent.abs();
+    let contact_lens = contact_lens_aniseikonia_percent.abs();
+    if spectacle > ANISEIKONIA_SYMPTOMATIC_PERCENT {
+        if contact_lens < spectacle {
+            suggest(AnisometropiaOption::ContactLenses);
+        }
+        if spectacle.min(contact_lens) > ANISEIKONIA_SYMPTOMATIC_PERCENT {
+            suggest(AnisometropiaOption::IseikonicLenses);
+        }
+    }
+    if vertical_imbalance_at_reading.abs() > SLAB_OFF_THRESHOLD {
+        suggest(AnisometropiaOption::ContactLenses);
+        suggest(AnisometropiaOption::SlabOff);
+        suggest(AnisometropiaOption::SeparateReadingGlasses);
+    }
+    if spectacle > ANISEIKONIA_NOTICEABLE_PERCENT {
+        suggest(AnisometropiaOption::MinimizeVertexDistance);
+    }
+
+    AnisometropiaAssessment {
+        spherical_equivalent_difference: od.spherical_equivalent() - os.spherical_equivalent(),
+        max_meridional_difference,
+        worst_meridian_deg,
+        spectacle_aniseikonia_percent,
+        contact_lens_aniseikonia_percent,
+        vertical_imbalance_at_reading,
+        severity: AnisometropiaSeverity::from_difference(max_meridional_difference),
+        suggestions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn worst_meridian_matches_a_power_at_scan() {
+        let od = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -2.5,
+            axis_deg: 30.0,
+        };
+        let os = SpheroCyl {
+            sphere: -0.5,
+            cylinder: -0.75,
+            axis_deg: 150.0,
+        };
+        let assessment = assess_anisometropia(od, os, AnisometropiaSetup::default());
+        let scanned = (0..1800)
+            .map(|i| {
+                let phi = i as f64 / 10.0;
+                (od.power_at(phi) - os.power_at(phi)).abs()
+            })
+            .fold(0.0, f64::max);
+        assert_abs_diff_eq!(
+            assessment.max_meridional_difference,
+            scanned,
+            epsilon = 1e-4
+        );
+        assert!(assessment.worst_meridian_deg > 0.0 && assessment.worst_meridian_deg <= 180.0);
+    }
+
+    #[test]
+    fn cylinder_only_anisometropia_hides_in_the_spherical_equivalent() {
+        // Equal spherical equivalents, but 1.00 D apart at 90°
+        let od = SpheroCyl {
+            sphere: 0.0,
+            cylinder: -2.0,
+            axis_deg: 180.0,
+        };
+        let os = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let assessment = assess_anisometropia(od, os, AnisometropiaSetup::default());
+        assert_abs_diff_eq!(
+            assessment.spherical_equivalent_difference,
+            0.0,
+            epsilon = 1e-12
+        );
+        assert_abs_diff_eq!(assessment.max_meridional_difference, 1.0, epsilon = 1e-12);
+        assert_eq!(assessment.severity, AnisometropiaSeverity::Mild);
+        // Vertical meridian: OD −2.00, OS −1.00 → 1Δ imbalance at 10 mm
+        assert_abs_diff_eq!(
+            assessment.vertical_imbalance_at_reading,
+            -1.0,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn isometropia_needs_nothing() {
+        let rx = SpheroCyl {
+            sphere: -3.0,
+            cylinder: -0.5,
+            axis_deg: 90.0,
+        };
+        let assessment = assess_anisometropia(rx, rx, AnisometropiaSetup::default());
+        assert_eq!(assessment.severity, AnisometropiaSeverity::Negligible);
+        assert_abs_diff_eq!(
+            assessment.spectacle_aniseikonia_percent,
+            0.0,
+            epsilon = 1e-12
+        );
+        assert!(assessment.suggestions.is_empty());
+    }
+}
@@ -0,0 +1,221 @@
+//! Corneal and refractive astigmatism as a magnitude and steep meridian.
+//!
+//! ## Overview
+//! Surgical planning and outcome analysis describe astigmatism by its
+//! magnitude and the meridian in which the eye is steepest, regardless of
+//! whether it was measured with a keratometer or by refraction.
+//!
+//! ## Conventions
+//! - Magnitudes are in **diopters (D)** and always non-negative.
+//! - The steep meridian is in **degrees [0,180)**.
+//! - A refraction's steep meridian is its **plus-cylinder axis**.
+//! - Double-angle vectors use `x = M·cos(2θ)`, `y = M·sin(2θ)`.
+
+use crate::*;
+
+/// Keratometry readings of the anterior cornea.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct Keratometry {
+    /// Power of the flattest meridian (D).
+    /// Example: 43.00 D @ 180 → `flat_k = 43.0`.
+    pub flat_k: f64,
+
+    /// Power of the steepest meridian (D).
+    /// Example: 44.50 D @ 90 → `steep_k = 44.5`.
+    pub steep_k: f64,
+
+    /// Meridian of the steepest power in degrees, range [0, 180).
+    pub steep_axis_deg: f64,
+}
+
+impl Keratometry {
+    /// Returns the meridian of the flattest power, 90° away from the steep meridian.
+    pub fn flat_axis_deg(&self) -> f64 {
+        (self.steep_axis_deg + 90.0).rem_euclid(180.0)
+    }
+
+    /// Returns the corneal power as a minus-cylinder [`SpheroCyl`].
+    ///
+    /// The sphere is the steep K and the axis is the steep meridian, so
+    /// [`SpheroCyl::power_at`] returns the keratometric power of any meridian.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let k = Keratometry { flat_k: 43.0, steep_k: 44.5, steep_axis_deg: 90.0 };
+    /// let power = k.to_sphero_cyl();
+    /// assert_eq!(power.power_at(90.0), 44.5);
+    /// assert_eq!(power.power_at(0.0), 43.0);
+    /// ```
+    pub fn to_sphero_cyl(&self) -> SpheroCyl {
+        SpheroCyl {
+            sphere: self.steep_k,
+            cylinder: self.flat_k - self.steep_k,
+            axis_deg: self.steep_axis_deg,
+        }
+    }
+}
+
+/// Astigmatism described by its magnitude and steep meridian.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct Astigmatism {
+    /// Magnitude of the astigmatism (D), always non-negative.
+    pub magnitude: f64,
+
+    /// Steep meridian in degrees, range [0, 180).
+    pub axis_deg: f64,
+}
+
+impl Astigmatism {
+    /// Creates a new [`Astigmatism`], folding a negative magnitude onto the
+    /// orthogonal meridian and normalizing the axis into [0, 180).
+    pub fn new(magnitude: f64, axis_deg: f64) -> Self {
+        let (magnitude, axis_deg) = if magnitude < 0.0 {
+            (-magnitude, axis_deg + 90.0)
+        } else {
+            (magnitude, axis_deg)
+        };
+
+        // Fold rounding noise just below 180° back onto 0°
+        let axis_deg = axis_deg.rem_euclid(180.0);
+        let axis_deg = if 180.0 - axis_deg < 1e-9 { 0.0 } else { axis_deg };
+
+        Self {
+            magnitude,
+            axis_deg,
+        }
+    }
+
+    /// Returns the astigmatic part of a refraction.
+    ///
+    /// The steep meridian of the eye is the plus-cylinder axis of the correcting lens,
+    /// so minus-cylinder refractions are rotated by 90°.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// // −1.00 DC × 180 corrects with-the-rule astigmatism, steep at 90°
+    /// let rx = SpheroCyl { sphere: -2.0, cylinder: -1.0, axis_deg: 180.0 };
+    /// let astig = Astigmatism::from_refraction(rx);
+    /// assert_eq!(astig.magnitude, 1.0);
+    /// assert_eq!(astig.axis_deg, 90.0);
+    /// ```
+    pub fn from_refraction(rx: SpheroCyl) -> Self {
+        Self::new(rx.cylinder, rx.axis_deg)
+    }
+
+    /// Returns the astigmatism as a cylinder-only power, in minus-cylinder form
+    /// with the axis along the steep meridian.
+    ///
+    /// The steep meridian has zero power and the flat meridian has `-magnitude`,
+    /// which allows combining with other powers via [`crossed_cylinders`].
+    pub fn to_sphero_cyl(&self) -> SpheroCyl {
+        SpheroCyl {
+            sphere: 0.0,
+            cylinder: -self.magnitude,
+            axis_deg: self.axis_deg,
+        }
+    }
+
+    /// Returns the astigmatism in double-angle space as `(x, y)`.
+    ///
+    /// Doubling the angle makes meridians 180° apart coincide, so vectors can be
+    /// added, subtracted and averaged with ordinary Cartesian arithmetic.
+    pub fn double_angle(&self) -> (f64, f64) {
+        let two_theta = (2.0 * self.axis_deg).to_radians();
+        (
+            self.magnitude * two_theta.cos(),
+            self.magnitude * two_theta.sin(),
+        )
+    }
+
+    /// Creates an [`Astigmatism`] from a double-angle vector `(x, y)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let astig = Astigmatism::new(1.5, 30.0);
+    /// let (x, y) = astig.double_angle();
+    /// let back = Astigmatism::from_double_angle(x, y);
+    /// assert_abs_diff_eq!(back.magnitude, 1.5, epsilon = 1e-12);
+    /// assert_abs_diff_eq!(back.axis_deg, 30.0, epsilon = 1e-12);
+    /// ```
+    pub fn from_double_angle(x: f64, y: f64) -> Self {
+        let axis_deg = y.atan2(x).to_degrees() / 2.0;
+        Self::new(x.hypot(y), axis_deg)
+    }
+}
+
+impl From<Keratometry> for Astigmatism {
+    fn from(k: Keratometry) -> Self {
+        Self::new(k.steep_k - k.flat_k, k.steep_axis_deg)
+    }
+}
+
+impl From<SpheroCyl> for Astigmatism {
+    /// Equivalent to [`Astigmatism::from_refraction`].
+    fn from(rx: SpheroCyl) -> Self {
+        Self::from_refraction(rx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn keratometry_to_astigmatism() {
+        let k = Keratometry {
+            flat_k: 42.25,
+            steep_k: 43.75,
+            steep_axis_deg: 95.0,
+        };
+        let a = Astigmatism::from(k);
+        assert_abs_diff_eq!(a.magnitude, 1.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.axis_deg, 95.0, epsilon = EPS);
+        assert_abs_diff_eq!(k.flat_axis_deg(), 5.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn minus_and_plus_cylinder_refractions_agree() {
+        let minus = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -2.0,
+            axis_deg: 30.0,
+        };
+        let a = Astigmatism::from_refraction(minus);
+        let b = Astigmatism::from_refraction(minus.transpose());
+        assert_abs_diff_eq!(a.magnitude, b.magnitude, epsilon = EPS);
+        assert_abs_diff_eq!(a.axis_deg, b.axis_deg, epsilon = EPS);
+        assert_abs_diff_eq!(a.axis_deg, 120.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn double_angle_cancels_orthogonal_astigmatism() {
+        let a = Astigmatism::new(1.0, 90.0).double_angle();
+        let b = Astigmatism::new(1.0, 180.0).double_angle();
+        let sum = Astigmatism::from_double_angle(a.0 + b.0, a.1 + b.1);
+        assert_abs_diff_eq!(sum.magnitude, 0.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn sphero_cyl_power_is_zero_along_steep_meridian() {
+        let a = Astigmatism::new(2.0, 45.0);
+        let p = a.to_sphero_cyl();
+        assert_abs_diff_eq!(p.power_at(45.0), 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(p.power_at(135.0), -2.0, epsilon = EPS);
+    }
+}
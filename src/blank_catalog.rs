@@ -0,0 +1,373 @@
+//! Stock blank availability.
+//!
+//! ## Overview
+//! A lab stocks semi-finished or finished blanks in a limited set of diameters
+//! for each material and base curve, and each base curve covers a range of
+//! powers. [`BlankCatalog::find_blanks`] checks whether a lens can be made from
+//! stock and returns the candidate blanks, or the reason nothing fits.
+//!
+//! ## Method
+//! Blanks are filtered in three stages, and the first stage that leaves no
+//! blank determines the reason reported:
+//! ```text
+//! 1. Material and base curve      → BlankUnavailable::NoMatchingBase
+//! 2. Power range (minus-cyl form) → BlankUnavailable::OutOfPowerRange
+//! 3. Usable diameter ≥ required   → BlankUnavailable::TooLarge
+//! ```
+//!
+//! ## Usable Diameter
+//! - **Round**: the blank diameter.
+//! - **Decentered**: the blank diameter plus twice the optical center offset,
+//!   assuming the offset is oriented toward the lens decentration.
+//! - **Elliptical**: the minor diameter, since the orientation of the lens
+//!   shape within the blank is not known.
+//!
+//! Compare against the minimum blank size from
+//! [`minimum_blank_size`](crate::minimum_blank_size) or
+//! [`per_eye_blank_size`](crate::per_eye_blank_size).
+
+use std::fmt;
+
+use crate::*;
+
+/// Tolerance when matching a requested base curve against the catalog (D).
+const BASE_CURVE_TOLERANCE: f64 = 1e-6;
+
+/// Shape of a stock blank.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum BlankShape {
+    /// Round blank with the optical center at its geometric center.
+    Round,
+
+    /// Round blank with the optical center displaced from its geometric center.
+    Decentered {
+        /// Distance from the geometric center to the optical center (mm).
+        offset_mm: f64,
+    },
+
+    /// Elliptical blank; `diameter_mm` of the [`StockBlank`] is the major diameter.
+    Elliptical {
+        /// Minor diameter (mm).
+        minor_diameter_mm: f64,
+    },
+}
+
+/// A blank listed in a lab's stock catalog.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct StockBlank {
+    /// Lens material.
+    pub material: LensMaterial,
+
+    /// Nominal base curve (D).
+    pub base_curve: f64,
+
+    /// Blank diameter (mm). For elliptical blanks, the major diameter.
+    pub diameter_mm: f64,
+
+    /// Blank shape.
+    pub shape: BlankShape,
+
+    /// Lowest sphere covered, in minus-cylinder form (D).
+    pub min_sphere: f64,
+
+    /// Highest sphere covered, in minus-cylinder form (D).
+    pub max_sphere: f64,
+
+    /// Largest cylinder magnitude covered (D).
+    pub max_cylinder: f64,
+}
+
+impl StockBlank {
+    /// Returns the largest blank size this blank can substitute for (mm).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let blank = StockBlank {
+    ///     material: LensMaterial::Cr39,
+    ///     base_curve: 4.0,
+    ///     diameter_mm: 65.0,
+    ///     shape: BlankShape::Decentered { offset_mm: 3.0 },
+    ///     min_sphere: -6.0,
+    ///     max_sphere: 0.0,
+    ///     max_cylinder: 2.0,
+    /// };
+    /// assert_eq!(blank.usable_diameter_mm(), 71.0);
+    /// ```
+    pub fn usable_diameter_mm(&self) -> f64 {
+        match self.shape {
+            BlankShape::Round => self.diameter_mm,
+            BlankShape::Decentered { offset_mm } => self.diameter_mm + 2.0 * offset_mm,
+            BlankShape::Elliptical { minor_diameter_mm } => minor_diameter_mm,
+        }
+    }
+
+    /// Returns `true` if the blank's power range covers `rx`.
+    pub fn covers_power(&self, rx: SpheroCyl) -> bool {
+        let minus_cyl = if rx.cylinder > 0.0 {
+            rx.transpose()
+        } else {
+            rx
+        };
+        minus_cyl.sphere >= self.min_sphere
+            && minus_cyl.sphere <= self.max_sphere
+            && minus_cyl.cylinder.abs() <= self.max_cylinder
+    }
+}
+
+/// Reason no stock blank can be used.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum BlankUnavailable {
+    /// No blank is stocked in the material with the requested base curve.
+    NoMatchingBase,
+
+    /// Blanks exist in the material, but none covers the Rx.
+    OutOfPowerRange,
+
+    /// Blanks cover the Rx, but none is large enough.
+    TooLarge {
+        /// Largest usable diameter among the blanks covering the Rx (mm).
+        largest_available_mm: f64,
+    },
+}
+
+impl fmt::Display for BlankUnavailable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BlankUnavailable::NoMatchingBase => {
+                write!(f, "no stock blank in this material and base curve")
+            }
+            BlankUnavailable::OutOfPowerRange => {
+                write!(f, "Rx is outside the power range of stock blanks")
+            }
+            BlankUnavailable::TooLarge {
+                largest_available_mm,
+            } => write!(
+                f,
+                "required blank exceeds largest stock blank ({largest_available_mm} mm)"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BlankUnavailable {}
+
+/// A lab's stock blank catalog.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct BlankCatalog {
+    /// Blanks in stock.
+    pub blanks: Vec<StockBlank>,
+}
+
+impl BlankCatalog {
+    /// Creates a catalog from a list of blanks.
+    pub fn new(blanks: Vec<StockBlank>) -> Self {
+        Self { blanks }
+    }
+
+    /// Find stock blanks that can produce `rx` in `material` at the required size.
+    ///
+    /// If `base_curve` is `None`, any base curve is accepted. Candidates are
+    /// returned smallest usable diameter first.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    ///
+    /// let blank = StockBlank {
+    ///     material: LensMaterial::Polycarbonate,
+    ///     base_curve: 4.0,
+    ///     diameter_mm: 70.0,
+    ///     shape: BlankShape::Round,
+    ///     min_sphere: -8.0,
+    ///     max_sphere: -2.0,
+    ///     max_cylinder: 4.0,
+    /// };
+    /// let catalog = BlankCatalog::new(vec![blank]);
+    /// let rx = SpheroCyl { sphere: -3.0, cylinder: -1.0, axis_deg: 180.0 };
+    ///
+    /// let found = catalog.find_blanks(rx, LensMaterial::Polycarbonate, None, 66.0);
+    /// assert_eq!(found.unwrap().len(), 1);
+    ///
+    /// let too_large = catalog.find_blanks(rx, LensMaterial::Polycarbonate, None, 72.0);
+    /// assert_eq!(
+    ///     too_large.unwrap_err(),
+    ///     BlankUnavailable::TooLarge { largest_available_mm: 70.0 }
+    /// );
+    /// ```
+    pub fn find_blanks(
+        &self,
+        rx: SpheroCyl,
+        material: LensMaterial,
+        base_curve: Option<f64>,
+        required_mm: f64,
+    ) -> Result<Vec<StockBlank>, BlankUnavailable> {
+        let matching_base: Vec<&StockBlank> = self
+            .blanks
+            .iter()
+            .filter(|b| b.material == material)
+            .filter(|b| {
+                base_curve.is_none_or(|bc| (b.base_curve - bc).abs() < BASE_CURVE_TOLERANCE)
+            })
+            .collect();
+        if matching_base.is_empty() {
+            return Err(BlankUnavailable::NoMatchingBase);
+        }
+
+        let in_range: Vec<&StockBlank> = matching_base
+            .into_iter()
+            .filter(|b| b.covers_power(rx))
+            .collect();
+        if in_range.is_empty() {
+            return Err(BlankUnavailable::OutOfPowerRange);
+        }
+
+        let mut candidates: Vec<StockBlank> = in_range
+            .iter()
+            .filter(|b| b.usable_diameter_mm() >= required_mm)
+            .map(|b| **b)
+            .collect();
+        if candidates.is_empty() {
+            let largest_available_mm = in_range
+                .iter()
+                .map(|b| b.usable_diameter_mm())
+                .fold(f64::NEG_INFINITY, f64::max);
+            return Err(BlankUnavailable::TooLarge {
+                largest_available_mm,
+            });
+        }
+
+        candidates.sort_by(|a, b| a.usable_diameter_mm().total_cmp(&b.usable_diameter_mm()));
+        Ok(candidates)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn blank(base_curve: f64, diameter_mm: f64, shape: BlankShape) -> StockBlank {
+        StockBlank {
+            material: LensMaterial::Cr39,
+            base_curve,
+            diameter_mm,
+            shape,
+            min_sphere: -6.0,
+            max_sphere: 0.0,
+            max_cylinder: 2.0,
+        }
+    }
+
+    fn catalog() -> BlankCatalog {
+        BlankCatalog::new(vec![
+            blank(4.0, 70.0, BlankShape::Round),
+            blank(4.0, 65.0, BlankShape::Round),
+            blank(4.0, 65.0, BlankShape::Decentered { offset_mm: 4.0 }),
+            blank(
+                4.0,
+                80.0,
+                BlankShape::Elliptical {
+                    minor_diameter_mm: 60.0,
+                },
+            ),
+            StockBlank {
+                max_sphere: 4.0,
+                min_sphere: 0.25,
+                ..blank(6.0, 70.0, BlankShape::Round)
+            },
+        ])
+    }
+
+    #[test]
+    fn candidates_sorted_by_usable_diameter() {
+        let rx = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -1.0,
+            axis_deg: 90.0,
+        };
+        let found = catalog()
+            .find_blanks(rx, LensMaterial::Cr39, Some(4.0), 62.0)
+            .unwrap();
+        let sizes: Vec<f64> = found.iter().map(|b| b.usable_diameter_mm()).collect();
+        assert_eq!(sizes, vec![65.0, 70.0, 73.0]);
+    }
+
+    #[test]
+    fn plus_cyl_rx_is_checked_in_minus_cyl_form() {
+        // −0.50 / −1.50 × 180 in minus-cyl form is in range of the 4.00 base
+        let rx = SpheroCyl {
+            sphere: -2.0,
+            cylinder: 1.5,
+            axis_deg: 90.0,
+        };
+        let found = catalog()
+            .find_blanks(rx, LensMaterial::Cr39, Some(4.0), 60.0)
+            .unwrap();
+        assert_eq!(found.len(), 4);
+    }
+
+    #[test]
+    fn reports_no_matching_base() {
+        let cat = catalog();
+        let rx = SpheroCyl {
+            sphere: -2.0,
+            cylinder: 0.0,
+            axis_deg: 90.0,
+        };
+        assert_eq!(
+            cat.find_blanks(rx, LensMaterial::Cr39, Some(8.0), 60.0)
+                .unwrap_err(),
+            BlankUnavailable::NoMatchingBase
+        );
+        assert_eq!(
+            cat.find_blanks(rx, LensMaterial::Trivex, None, 60.0)
+                .unwrap_err(),
+            BlankUnavailable::NoMatchingBase
+        );
+    }
+
+    #[test]
+    fn reports_out_of_power_range() {
+        let rx = SpheroCyl {
+            sphere: -7.0,
+            cylinder: -0.5,
+            axis_deg: 90.0,
+        };
+        let err = catalog()
+            .find_blanks(rx, LensMaterial::Cr39, Some(4.0), 60.0)
+            .unwrap_err();
+        assert_eq!(err, BlankUnavailable::OutOfPowerRange);
+    }
+
+    #[test]
+    fn reports_largest_available_when_too_large() {
+        let rx = SpheroCyl {
+            sphere: 2.0,
+            cylinder: -0.5,
+            axis_deg: 90.0,
+        };
+        let err = catalog()
+            .find_blanks(rx, LensMaterial::Cr39, None, 75.0)
+            .unwrap_err();
+        assert_eq!(
+            err,
+            BlankUnavailable::TooLarge {
+                largest_available_mm: 70.0
+            }
+        );
+    }
+}
@@ -0,0 +1,256 @@
+//! Chromatic aberration of spectacle lenses.
+//!
+//! ## Overview
+//! - **Longitudinal chromatic aberration (LCA)**: the spread of power between
+//!   the blue and red ends of the spectrum.
+//! - **Transverse chromatic aberration (TCA)**: the spread of prism seen away
+//!   from the optical center, which wearers notice as color fringes.
+//! - [`assess_chromatic_aberration`] checks the TCA across a zone of the lens
+//!   against a threshold and suggests a material with a higher Abbe number.
+//!
+//! ## Formulas
+//! ```text
+//! LCA = F / V          (D)
+//! TCA = Prism / V      (Δ), with Prism from Prentice's rule
+//! ```
+//! where V is the Abbe number of the material.
+//!
+//! ## Clinical Notes
+//! - A TCA of about 0.1Δ is commonly taken as the threshold of noticeable
+//!   color fringing.
+//! - The prism is computed with [`induced_prism`], so cylinder cross-terms and
+//!   OD/OS conventions match the rest of the crate.
+
+use crate::*;
+
+/// Commonly used threshold for noticeable transverse chromatic aberration (Δ).
+pub const DEFAULT_TCA_THRESHOLD: f64 = 0.1;
+
+/// Number of directions sampled around the edge of a zone.
+const ZONE_SAMPLES: usize = 360;
+
+/// Result of a chromatic aberration check.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ChromaticAssessment {
+    /// Longitudinal chromatic aberration of the strongest principal meridian (D).
+    pub longitudinal: f64,
+
+    /// Largest transverse chromatic aberration at the edge of the zone (Δ).
+    pub max_transverse: f64,
+
+    /// Decentration at which the largest TCA occurs.
+    pub worst_point: Decentration,
+
+    /// `true` if `max_transverse` exceeds the threshold.
+    pub exceeds_threshold: bool,
+
+    /// Material with a higher Abbe number to consider, if the threshold is exceeded.
+    ///
+    /// This is the highest-index material that keeps the zone within the
+    /// threshold, or the highest-Abbe material if none does.
+    pub suggested_material: Option<LensMaterial>,
+}
+
+/// Compute longitudinal chromatic aberration, `F / V`.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // −6.00 D in polycarbonate (V = 30)
+/// assert_abs_diff_eq!(longitudinal_chromatic_aberration(-6.0, 30.0), -0.2, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `abbe_number` is not positive.
+pub fn longitudinal_chromatic_aberration(power: f64, abbe_number: f64) -> f64 {
+    debug_assert!(abbe_number > 0.0, "abbe_number must be positive");
+    power / abbe_number
+}
+
+/// Compute transverse chromatic aberration at a point of the lens, `Prism / V` (Δ).
+///
+/// `dec` is the position of the point relative to the optical center, using the
+/// same conventions as [`induced_prism`].
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // −6.00 D polycarbonate, 10 mm from the optical center: 6Δ / 30 = 0.2Δ
+/// let lens = SpheroCyl { sphere: -6.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let dec = Decentration { horizontal_mm: -10.0, vertical_mm: 0.0 };
+/// let tca = transverse_chromatic_aberration(Eye::OD, lens, dec, 30.0);
+/// assert_abs_diff_eq!(tca, 0.2, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `abbe_number` is not positive.
+pub fn transverse_chromatic_aberration(
+    eye: Eye,
+    lens: SpheroCyl,
+    dec: Decentration,
+    abbe_number: f64,
+) -> f64 {
+    debug_assert!(abbe_number > 0.0, "abbe_number must be positive");
+    induced_prism(eye, lens, dec).magnitude() / abbe_number
+}
+
+/// Returns the largest prism magnitude on a circle around the optical center
+/// and where it occurs.
+fn max_prism_on_zone(eye: Eye, lens: SpheroCyl, zone_radius_mm: f64) -> (f64, Decentration) {
+    (0..ZONE_SAMPLES)
+        .map(|i| {
+            let (sin, cos) = (i as f64 * 360.0 / ZONE_SAMPLES as f64)
+                .to_radians()
+                .sin_cos();
+            let dec = Decentration {
+                horizontal_mm: zone_radius_mm * cos,
+                vertical_mm: zone_radius_mm * sin,
+            };
+            (induced_prism(eye, lens, dec).magnitude(), dec)
+        })
+        .max_by(|a, b| a.0.total_cmp(&b.0))
+        .expect("zone has samples")
+}
+
+/// Check chromatic aberration across a circular zone around the optical center.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let lens = SpheroCyl { sphere: -6.0, cylinder: -1.0, axis_deg: 90.0 };
+/// let check = assess_chromatic_aberration(
+///     Eye::OD,
+///     lens,
+///     LensMaterial::Polycarbonate,
+///     10.0,
+///     DEFAULT_TCA_THRESHOLD,
+/// );
+/// assert!(check.exceeds_threshold);
+/// let better = check.suggested_material.unwrap();
+/// assert!(better.abbe_number() > LensMaterial::Polycarbonate.abbe_number());
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `zone_radius_mm` is negative.
+pub fn assess_chromatic_aberration(
+    eye: Eye,
+    lens: SpheroCyl,
+    material: LensMaterial,
+    zone_radius_mm: f64,
+    threshold: f64,
+) -> ChromaticAssessment {
+    debug_assert!(zone_radius_mm >= 0.0, "zone_radius_mm must be non-negative");
+
+    let abbe = material.abbe_number();
+    let strongest = lens.sphere.abs().max((lens.sphere + lens.cylinder).abs());
+    let (max_prism, worst_point) = max_prism_on_zone(eye, lens, zone_radius_mm);
+    let max_transverse = max_prism / abbe;
+    let exceeds_threshold = max_transverse > threshold;
+
+    let suggested_material = if exceeds_threshold {
+        let higher_abbe = LensMaterial::ALL
+            .into_iter()
+            .filter(|m| m.abbe_number() > abbe);
+        higher_abbe
+            .clone()
+            .filter(|m| max_prism / m.abbe_number() <= threshold)
+            .max_by(|a, b| a.index().total_cmp(&b.index()))
+            .or_else(|| higher_abbe.max_by(|a, b| a.abbe_number().total_cmp(&b.abbe_number())))
+    } else {
+        None
+    };
+
+    ChromaticAssessment {
+        longitudinal: longitudinal_chromatic_aberration(strongest, abbe),
+        max_transverse,
+        worst_point,
+        exceeds_threshold,
+        suggested_material,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn zone_maximum_follows_strongest_meridian() {
+        // −2.00 −4.00 × 180: −6.00 D in the vertical meridian
+        let lens = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -4.0,
+            axis_deg: 180.0,
+        };
+        let check = assess_chromatic_aberration(Eye::OS, lens, LensMaterial::Cr39, 10.0, 1.0);
+        assert_abs_diff_eq!(check.max_transverse, 6.0 / 58.0, epsilon = EPS);
+        assert_abs_diff_eq!(check.worst_point.horizontal_mm.abs(), 0.0, epsilon = 1e-6);
+        assert_abs_diff_eq!(check.longitudinal, 6.0 / 58.0, epsilon = EPS);
+        assert!(!check.exceeds_threshold);
+        assert!(check.suggested_material.is_none());
+    }
+
+    #[test]
+    fn suggests_highest_index_material_within_threshold() {
+        // 4Δ at the zone edge: needs V ≥ 40 for 0.1Δ
+        let lens = SpheroCyl {
+            sphere: 4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let check = assess_chromatic_aberration(
+            Eye::OD,
+            lens,
+            LensMaterial::HighIndex167,
+            10.0,
+            DEFAULT_TCA_THRESHOLD,
+        );
+        assert!(check.exceeds_threshold);
+        assert_eq!(check.suggested_material, Some(LensMaterial::HighIndex160));
+    }
+
+    #[test]
+    fn suggests_highest_abbe_when_nothing_meets_threshold() {
+        let lens = SpheroCyl {
+            sphere: -12.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let check = assess_chromatic_aberration(
+            Eye::OD,
+            lens,
+            LensMaterial::Polycarbonate,
+            15.0,
+            DEFAULT_TCA_THRESHOLD,
+        );
+        assert_eq!(check.suggested_material, Some(LensMaterial::CrownGlass));
+    }
+
+    #[test]
+    fn tca_is_zero_at_optical_center() {
+        let lens = SpheroCyl {
+            sphere: 8.0,
+            cylinder: -2.0,
+            axis_deg: 45.0,
+        };
+        let dec = Decentration {
+            horizontal_mm: 0.0,
+            vertical_mm: 0.0,
+        };
+        assert_eq!(
+            transverse_chromatic_aberration(Eye::OD, lens, dec, 30.0),
+            0.0
+        );
+    }
+}
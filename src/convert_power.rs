@@ -68,6 +68,67 @@ pub fn simulate_lensmeter_reading(
     }
 }
 
+/// Reference wavelength of a power statement.
+///
+/// US labs state powers at nd, European labs at ne; the same lens reads
+/// slightly stronger at ne because the index is higher.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum ReferenceWavelength {
+    /// Sodium D-line, 589.3 nm.
+    Nd,
+
+    /// Mercury e-line, 546.07 nm.
+    Ne,
+}
+
+impl ReferenceWavelength {
+    /// Returns the wavelength (nm).
+    pub fn wavelength_nm(&self) -> f64 {
+        match self {
+            ReferenceWavelength::Nd => WAVELENGTH_D_NM,
+            ReferenceWavelength::Ne => WAVELENGTH_E_NM,
+        }
+    }
+}
+
+/// Restate a lens power given at one reference wavelength at another.
+///
+/// The lens itself does not change, only the wavelength its power is quoted at,
+/// so this is [`convert_power`] with the material's index at each wavelength.
+///
+/// Example:
+/// A polycarbonate lens stated as -6.00 D at nd is about -6.05 D at ne.
+pub fn convert_power_wavelength(
+    power_diopters: f64,
+    material: Dispersion,
+    from: ReferenceWavelength,
+    to: ReferenceWavelength,
+) -> f64 {
+    convert_power(
+        power_diopters,
+        material.index_at(from.wavelength_nm()),
+        material.index_at(to.wavelength_nm()),
+    )
+}
+
+/// Restate a full sph-cyl-axis Rx between reference wavelengths.
+/// Axis is returned unchanged.
+pub fn convert_rx_wavelength(
+    rx: SpheroCyl,
+    material: Dispersion,
+    from: ReferenceWavelength,
+    to: ReferenceWavelength,
+) -> SpheroCyl {
+    SpheroCyl {
+        sphere: convert_power_wavelength(rx.sphere, material, from, to),
+        cylinder: convert_power_wavelength(rx.cylinder, material, from, to),
+        axis_deg: rx.axis_deg,
+    }
+}
+
 
 #[cfg(test)]
 mod tests {
@@ -190,4 +251,28 @@ mod tests {
         // Compare against a hard-coded reference using relative tolerance
         assert_relative_eq!(factor, 1.120458, max_relative = 1e-6);
     }
+
+    #[test]
+    fn convert_power_wavelength_nd_to_ne_poly() {
+        let poly = LensMaterial::Polycarbonate.dispersion();
+        let ne = poly.index_at(WAVELENGTH_E_NM);
+        let out = convert_power_wavelength(-6.00, poly, ReferenceWavelength::Nd, ReferenceWavelength::Ne);
+
+        assert_abs_diff_eq!(out, -6.00 * (ne - 1.0) / (POLYCARBONATE_INDEX - 1.0), epsilon = 1e-12);
+        assert_abs_diff_eq!(out, -6.05, epsilon = 0.01);
+    }
+
+    #[test]
+    fn convert_rx_wavelength_round_trip() {
+        let material = LensMaterial::HighIndex174.dispersion();
+        let rx = SpheroCyl { sphere: -8.25, cylinder: -1.75, axis_deg: 165.0 };
+
+        let ne = convert_rx_wavelength(rx, material, ReferenceWavelength::Nd, ReferenceWavelength::Ne);
+        let back = convert_rx_wavelength(ne, material, ReferenceWavelength::Ne, ReferenceWavelength::Nd);
+
+        assert!(ne.sphere < rx.sphere);
+        assert_abs_diff_eq!(back.sphere, rx.sphere, epsilon = 1e-12);
+        assert_abs_diff_eq!(back.cylinder, rx.cylinder, epsilon = 1e-12);
+        assert_abs_diff_eq!(ne.axis_deg, 165.0, epsilon = 1e-12);
+    }
 }
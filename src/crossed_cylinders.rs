@@ -5,31 +5,26 @@ use crate::*;
 /// Combine two spherocylindrical lens prescriptions into a single, resultant prescription.
 /// If you have two lenses—each with its own sphere, cylinder power, and axis, compute the single lens power and orientation that would replicate the net effect of stacking or combining those two lenses.
 pub fn crossed_cylinders(lens1: SpheroCyl, lens2: SpheroCyl) -> SpheroCyl {
-    // Power matrix components for lens 1
-    let s1 = lens1.sphere;
-    let c1 = lens1.cylinder;
-    let a1 = lens1.axis_deg.to_radians();
-    let sin1 = a1.sin();
-    let cos1 = a1.cos();
-    let px1 = s1 + c1 * sin1 * sin1;
-    let pt1 = -c1 * sin1 * cos1;
-    let py1 = s1 + c1 * cos1 * cos1;
-
-    // Power matrix components for lens 2
-    let s2 = lens2.sphere;
-    let c2 = lens2.cylinder;
-    let a2 = lens2.axis_deg.to_radians();
-    let sin2 = a2.sin();
-    let cos2 = a2.cos();
-    let px2 = s2 + c2 * sin2 * sin2;
-    let pt2 = -c2 * sin2 * cos2;
-    let py2 = s2 + c2 * cos2 * cos2;
-
     // Resultant matrix: simple sum of power matrices
-    let px = px1 + px2;
-    let pt = pt1 + pt2;
-    let py = py1 + py2;
+    let [px1, pt1, py1] = power_matrix(lens1);
+    let [px2, pt2, py2] = power_matrix(lens2);
+    from_power_matrix([px1 + px2, pt1 + pt2, py1 + py2])
+}
 
+/// Dioptric power matrix `[Px, Pt, Py]` of a sphero-cylinder.
+///
+/// `Px` and `Py` are the curvital powers in the horizontal and vertical
+/// meridians and `Pt` is the torsional component.
+pub(crate) fn power_matrix(lens: SpheroCyl) -> [f64; 3] {
+    let s = lens.sphere;
+    let c = lens.cylinder;
+    let (sin, cos) = lens.axis_deg.to_radians().sin_cos();
+    [s + c * sin * sin, -c * sin * cos, s + c * cos * cos]
+}
+
+/// Sphero-cylinder in minus-cylinder form from a power matrix `[Px, Pt, Py]`,
+/// with the axis in [0, 180).
+pub(crate) fn from_power_matrix([px, pt, py]: [f64; 3]) -> SpheroCyl {
     // Invariants
     let trace = px + py;
     let determinant = (px * py) - (pt * pt);
@@ -39,11 +34,17 @@ pub fn crossed_cylinders(lens1: SpheroCyl, lens2: SpheroCyl) -> SpheroCyl {
     let cylinder = -delta;
     let sphere = (trace - cylinder) / 2.0;
 
-    // Axis from matrix orientation; use atan2 for numerical robustness
-    let axis_rad = (sphere - px).atan2(pt);
+    // Axis is the eigenvector of the sphere power: (Pt, S − Px) or, equivalently,
+    // (S − Py, Pt). Use the better conditioned one so an axis near 0° does not
+    // depend on rounding noise in S − Px.
+    let axis_rad = if sphere - py >= sphere - px {
+        pt.atan2(sphere - py)
+    } else {
+        (sphere - px).atan2(pt)
+    };
     let mut axis_deg = axis_rad.to_degrees();
     // Normalize to [0, 180)
-    axis_deg = axis_deg % 180.0;
+    axis_deg %= 180.0;
     if axis_deg < 0.0 {
         axis_deg += 180.0;
     }
@@ -136,4 +137,23 @@ mod tests {
         assert_abs_diff_eq!(ptr, pt_sum, epsilon = 1e-9);
         assert_abs_diff_eq!(pyr, py_sum, epsilon = 1e-9);
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn axis_near_horizontal_is_stable() {
+        // Rounding in sin(180°) must not rotate the axis
+        let lens1 = SpheroCyl {
+            sphere: -3.69,
+            cylinder: -1.09,
+            axis_deg: 180.0,
+        };
+        let lens2 = SpheroCyl {
+            sphere: 0.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let result = crossed_cylinders(lens1, lens2);
+        assert_abs_diff_eq!(result.sphere, -3.69, epsilon = 1e-12);
+        assert_abs_diff_eq!(result.cylinder, -1.09, epsilon = 1e-12);
+        assert!(result.axis_deg < 1e-6 || result.axis_deg > 180.0 - 1e-6);
+    }
+}
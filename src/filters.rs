@@ -0,0 +1,477 @@
+//! Tints, sunglass filters and their luminous transmittance.
+//!
+//! ## Overview
+//! [`assess_filter`] takes the spectral transmittance of a tint, optionally
+//! combined with the UV cutoff of a lens material, and reports:
+//! - luminous transmittance τv for CIE illuminant D65,
+//! - the ISO 12312-1 / ANSI Z80.3 filter category (0–4),
+//! - mean UV-A and UV-B transmittance,
+//! - traffic signal recognition and driving suitability.
+//!
+//! Photochromic lenses are assessed once per state, using the spectrum of the
+//! faded and the darkened lens.
+//!
+//! ## Formulas
+//! ```text
+//! τv = Σ τ(λ) V(λ) S_D65(λ) / Σ V(λ) S_D65(λ)          380–780 nm
+//! τ_signal = Σ τ(λ) V(λ) S_A(λ) τ_lamp(λ) / Σ V(λ) S_A(λ) τ_lamp(λ)
+//! Q = τ_signal / τv
+//! ```
+//!
+//! ## Thresholds
+//! ```text
+//! Category   τv
+//! 0          > 80%
+//! 1          > 43% to 80%
+//! 2          > 18% to 43%
+//! 3          > 8% to 18%
+//! 4          ≤ 8%
+//!
+//! Signal quotient Q:  red ≥ 0.8, yellow ≥ 0.8, green ≥ 0.6, blue ≥ 0.4
+//! Driving:            τv ≥ 8%, signals pass, τ(λ) ≥ 0.2 τv over 475–650 nm
+//! Night driving:      as above and τv ≥ 75%
+//! ```
+//!
+//! ## Conventions
+//! - Transmittances are fractions (0–1).
+//! - UV-B is 280–315 nm and UV-A is 315–380 nm; UV values are unweighted
+//!   means over each band.
+//! - Signal lights are illuminant A through idealized signal lenses
+//!   (red ≥ 610 nm, yellow ≥ 570 nm, green 470–560 nm, blue 420–500 nm),
+//!   which approximates the tabulated signal spectra of the standards.
+//! - ISO 12312-1 does not allow general-purpose filters below τv = 3%; such
+//!   filters are still reported as category 4.
+
+use crate::*;
+
+/// CIE illuminant D65 relative spectral power, 380–780 nm in 10 nm steps.
+pub const ILLUMINANT_D65: [f64; 41] = [
+    49.9755, 54.6482, 82.7549, 91.4860, 93.4318, 86.6823, 104.865, 117.008, 117.812, 114.861,
+    115.923, 108.811, 109.354, 107.802, 104.790, 107.689, 104.405, 104.046, 100.000, 96.3342,
+    95.7880, 88.6856, 90.0062, 89.5991, 87.6987, 83.2886, 83.6992, 80.0268, 80.2146, 82.2778,
+    78.2842, 69.7213, 71.6091, 74.3490, 61.6040, 69.8856, 75.0870, 63.5927, 46.4182, 66.8054,
+    63.3828,
+];
+
+/// Minimum signal quotients for red, yellow, green and blue lights.
+const SIGNAL_MINIMUMS: [f64; 4] = [0.8, 0.8, 0.6, 0.4];
+
+/// Pass bands of the idealized red, yellow, green and blue signal lenses (nm).
+const SIGNAL_BANDS: [(f64, f64); 4] = [
+    (610.0, VISIBLE_END_NM),
+    (570.0, VISIBLE_END_NM),
+    (470.0, 560.0),
+    (420.0, 500.0),
+];
+
+/// Wavelength step used for UV averages (nm).
+const UV_STEP_NM: f64 = 5.0;
+
+/// Transmittance at one wavelength.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SpectralTransmittance {
+    /// Wavelength (nm).
+    pub wavelength_nm: f64,
+
+    /// Transmittance (fraction, 0–1).
+    pub transmittance: f64,
+}
+
+/// Spectral transmittance curve of a tint or filter.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum FilterSpectrum {
+    /// Measured samples in ascending wavelength, interpolated linearly.
+    ///
+    /// Outside the sampled range the nearest end value is used.
+    Sampled(Vec<SpectralTransmittance>),
+
+    /// Neutral (gray) tint with the same transmittance at every wavelength.
+    Neutral {
+        /// Transmittance (fraction).
+        transmittance: f64,
+    },
+
+    /// Long-pass filter, blocking below the cutoff.
+    LongPass {
+        /// Cutoff wavelength (nm).
+        cutoff_nm: f64,
+
+        /// Transmittance above the cutoff (fraction).
+        transmittance: f64,
+    },
+}
+
+impl FilterSpectrum {
+    /// Returns the transmittance at a wavelength (nm).
+    ///
+    /// Returns `None` if a sampled spectrum is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let amber = FilterSpectrum::LongPass { cutoff_nm: 500.0, transmittance: 0.4 };
+    /// assert_eq!(amber.transmittance_at(450.0), Some(0.0));
+    /// assert_eq!(amber.transmittance_at(550.0), Some(0.4));
+    /// ```
+    pub fn transmittance_at(&self, wavelength_nm: f64) -> Option<f64> {
+        let transmittance = match self {
+            FilterSpectrum::Sampled(samples) => {
+                let i = samples.partition_point(|s| s.wavelength_nm <= wavelength_nm);
+                if i == 0 {
+                    return samples.first().map(|s| s.transmittance);
+                }
+                if i == samples.len() {
+                    return Some(samples[i - 1].transmittance);
+                }
+                let (a, b) = (samples[i - 1], samples[i]);
+                let t = (wavelength_nm - a.wavelength_nm) / (b.wavelength_nm - a.wavelength_nm);
+                a.transmittance + t * (b.transmittance - a.transmittance)
+            }
+            FilterSpectrum::Neutral { transmittance } => *transmittance,
+            FilterSpectrum::LongPass {
+                cutoff_nm,
+                transmittance,
+            } => {
+                if wavelength_nm < *cutoff_nm {
+                    0.0
+                } else {
+                    *transmittance
+                }
+            }
+        };
+        Some(transmittance)
+    }
+
+    /// Returns `true` if the spectrum is sampled without any samples.
+    fn is_empty(&self) -> bool {
+        matches!(self, FilterSpectrum::Sampled(samples) if samples.is_empty())
+    }
+}
+
+/// Filter category of ISO 12312-1 and ANSI Z80.3.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum FilterCategory {
+    /// Clear or very light tint, τv above 80%.
+    Category0,
+
+    /// Light tint, τv above 43% up to 80%.
+    Category1,
+
+    /// Medium sunglass, τv above 18% up to 43%.
+    Category2,
+
+    /// Dark sunglass, τv above 8% up to 18%.
+    Category3,
+
+    /// Very dark filter, τv of 8% or less; not for driving.
+    Category4,
+}
+
+impl FilterCategory {
+    /// Returns the category for a luminous transmittance (fraction).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// assert_eq!(FilterCategory::from_luminous_transmittance(0.15), FilterCategory::Category3);
+    /// ```
+    pub fn from_luminous_transmittance(luminous_transmittance: f64) -> Self {
+        match luminous_transmittance {
+            t if t > 0.80 => FilterCategory::Category0,
+            t if t > 0.43 => FilterCategory::Category1,
+            t if t > 0.18 => FilterCategory::Category2,
+            t if t > 0.08 => FilterCategory::Category3,
+            _ => FilterCategory::Category4,
+        }
+    }
+
+    /// Returns the category number, 0–4.
+    pub fn number(&self) -> u8 {
+        *self as u8
+    }
+}
+
+/// Relative visual attenuation quotients for traffic signal lights.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SignalQuotients {
+    /// Red signal quotient.
+    pub red: f64,
+
+    /// Yellow signal quotient.
+    pub yellow: f64,
+
+    /// Green signal quotient.
+    pub green: f64,
+
+    /// Blue signal quotient.
+    pub blue: f64,
+}
+
+impl SignalQuotients {
+    /// Returns `true` if every quotient meets its minimum.
+    pub fn pass(&self) -> bool {
+        [self.red, self.yellow, self.green, self.blue]
+            .iter()
+            .zip(SIGNAL_MINIMUMS)
+            .all(|(q, min)| *q >= min)
+    }
+}
+
+/// Result of a filter assessment.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct FilterAssessment {
+    /// Luminous transmittance for illuminant D65 (fraction).
+    pub luminous_transmittance: f64,
+
+    /// Filter category.
+    pub category: FilterCategory,
+
+    /// Mean transmittance over 315–380 nm (fraction).
+    pub uva_transmittance: f64,
+
+    /// Mean transmittance over 280–315 nm (fraction).
+    pub uvb_transmittance: f64,
+
+    /// Traffic signal quotients.
+    pub signal_quotients: SignalQuotients,
+
+    /// `true` if the filter is suitable for daytime driving.
+    pub suitable_for_driving: bool,
+
+    /// `true` if the filter is also suitable for twilight and night driving.
+    pub suitable_for_night_driving: bool,
+}
+
+/// Relative spectral power of CIE illuminant A (2856 K), 100 at 560 nm.
+fn illuminant_a(wavelength_nm: f64) -> f64 {
+    let c2: f64 = 1.435e7;
+    let t = 2848.0;
+    100.0
+        * (560.0 / wavelength_nm).powi(5)
+        * ((c2 / (t * 560.0)).exp_m1() / (c2 / (t * wavelength_nm)).exp_m1())
+}
+
+/// Returns the wavelengths of the tabulated visible spectrum.
+fn visible_wavelengths() -> impl Iterator<Item = f64> {
+    (0..PHOTOPIC_LUMINOSITY.len()).map(|i| VISIBLE_START_NM + i as f64 * VISIBLE_STEP_NM)
+}
+
+/// Weighted mean of `transmittance` over the visible spectrum.
+fn weighted_mean(transmittance: impl Fn(f64) -> f64, weight: impl Fn(usize, f64) -> f64) -> f64 {
+    let (sum, total) =
+        visible_wavelengths()
+            .enumerate()
+            .fold((0.0, 0.0), |(sum, total), (i, nm)| {
+                let w = weight(i, nm);
+                (sum + transmittance(nm) * w, total + w)
+            });
+    sum / total
+}
+
+/// Unweighted mean of `transmittance` over `[from_nm, to_nm]`.
+fn band_mean(transmittance: impl Fn(f64) -> f64, from_nm: f64, to_nm: f64) -> f64 {
+    let steps = ((to_nm - from_nm) / UV_STEP_NM).round() as usize;
+    let sum: f64 = (0..=steps)
+        .map(|i| transmittance(from_nm + i as f64 * UV_STEP_NM))
+        .sum();
+    sum / (steps + 1) as f64
+}
+
+/// Compute the luminous transmittance of a spectrum for illuminant D65 (fraction).
+///
+/// Returns `None` if a sampled spectrum is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// let gray = FilterSpectrum::Neutral { transmittance: 0.15 };
+/// assert_abs_diff_eq!(luminous_transmittance(&gray).unwrap(), 0.15, epsilon = 1e-12);
+/// ```
+pub fn luminous_transmittance(spectrum: &FilterSpectrum) -> Option<f64> {
+    if spectrum.is_empty() {
+        return None;
+    }
+    Some(weighted_mean(
+        |nm| spectrum.transmittance_at(nm).unwrap_or(0.0),
+        |i, _| PHOTOPIC_LUMINOSITY[i] * ILLUMINANT_D65[i],
+    ))
+}
+
+/// Assess a tint or filter, optionally combined with a lens material.
+///
+/// With a `material`, light below its [`LensMaterial::uv_cutoff_nm`] is
+/// blocked in addition to the tint.
+///
+/// Returns `None` if a sampled spectrum is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// // 15% gray sunglass tint in polycarbonate
+/// let tint = FilterSpectrum::Neutral { transmittance: 0.15 };
+/// let result = assess_filter(&tint, Some(LensMaterial::Polycarbonate)).unwrap();
+/// assert_eq!(result.category, FilterCategory::Category3);
+/// assert_eq!(result.uva_transmittance, 0.0);
+/// assert!(result.suitable_for_driving);
+/// assert!(!result.suitable_for_night_driving);
+/// ```
+pub fn assess_filter(
+    spectrum: &FilterSpectrum,
+    material: Option<LensMaterial>,
+) -> Option<FilterAssessment> {
+    if spectrum.is_empty() {
+        return None;
+    }
+    let cutoff_nm = material.map_or(0.0, |m| m.uv_cutoff_nm());
+    let transmittance = |nm: f64| {
+        if nm < cutoff_nm {
+            0.0
+        } else {
+            spectrum.transmittance_at(nm).unwrap_or(0.0)
+        }
+    };
+
+    let tv = weighted_mean(transmittance, |i, _| {
+        PHOTOPIC_LUMINOSITY[i] * ILLUMINANT_D65[i]
+    });
+    let quotient = |(from_nm, to_nm): (f64, f64)| {
+        let signal = weighted_mean(transmittance, |i, nm| {
+            if (from_nm..=to_nm).contains(&nm) {
+                PHOTOPIC_LUMINOSITY[i] * illuminant_a(nm)
+            } else {
+                0.0
+            }
+        });
+        if tv > 0.0 { signal / tv } else { 0.0 }
+    };
+    let signal_quotients = SignalQuotients {
+        red: quotient(SIGNAL_BANDS[0]),
+        yellow: quotient(SIGNAL_BANDS[1]),
+        green: quotient(SIGNAL_BANDS[2]),
+        blue: quotient(SIGNAL_BANDS[3]),
+    };
+
+    let min_visible = visible_wavelengths()
+        .filter(|nm| (475.0..=650.0).contains(nm))
+        .map(transmittance)
+        .fold(f64::INFINITY, f64::min);
+    let suitable_for_driving = tv >= 0.08 && signal_quotients.pass() && min_visible >= 0.2 * tv;
+
+    Some(FilterAssessment {
+        luminous_transmittance: tv,
+        category: FilterCategory::from_luminous_transmittance(tv),
+        uva_transmittance: band_mean(transmittance, 315.0, 380.0),
+        uvb_transmittance: band_mean(transmittance, 280.0, 315.0),
+        signal_quotients,
+        suitable_for_driving,
+        suitable_for_night_driving: suitable_for_driving && tv >= 0.75,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    fn sampled(points: &[(f64, f64)]) -> FilterSpectrum {
+        FilterSpectrum::Sampled(
+            points
+                .iter()
+                .map(|&(wavelength_nm, transmittance)| SpectralTransmittance {
+                    wavelength_nm,
+                    transmittance,
+                })
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn neutral_tint_passes_signals_and_keeps_its_category() {
+        let result = assess_filter(&FilterSpectrum::Neutral { transmittance: 0.3 }, None).unwrap();
+        assert_abs_diff_eq!(result.luminous_transmittance, 0.3, epsilon = EPS);
+        assert_eq!(result.category.number(), 2);
+        assert_abs_diff_eq!(result.signal_quotients.red, 1.0, epsilon = EPS);
+        assert_abs_diff_eq!(result.signal_quotients.blue, 1.0, epsilon = EPS);
+        assert_abs_diff_eq!(result.uvb_transmittance, 0.3, epsilon = EPS);
+        assert!(result.suitable_for_driving);
+    }
+
+    #[test]
+    fn category_boundaries() {
+        let cat = FilterCategory::from_luminous_transmittance;
+        assert_eq!(cat(0.81), FilterCategory::Category0);
+        assert_eq!(cat(0.80), FilterCategory::Category1);
+        assert_eq!(cat(0.43), FilterCategory::Category2);
+        assert_eq!(cat(0.18), FilterCategory::Category3);
+        assert_eq!(cat(0.08), FilterCategory::Category4);
+        assert_eq!(cat(0.02), FilterCategory::Category4);
+    }
+
+    #[test]
+    fn blue_blocking_filter_fails_signal_recognition() {
+        // Amber "night driving" filter cutting everything below 520 nm
+        let amber = FilterSpectrum::LongPass {
+            cutoff_nm: 520.0,
+            transmittance: 0.9,
+        };
+        let result = assess_filter(&amber, None).unwrap();
+        assert!(result.signal_quotients.blue < 0.4);
+        assert!(result.signal_quotients.red > 1.0);
+        assert!(!result.suitable_for_driving);
+        assert!(!result.suitable_for_night_driving);
+    }
+
+    #[test]
+    fn material_cutoff_blocks_uv() {
+        let clear = FilterSpectrum::Neutral {
+            transmittance: 0.92,
+        };
+        let crown = assess_filter(&clear, Some(LensMaterial::CrownGlass)).unwrap();
+        let poly = assess_filter(&clear, Some(LensMaterial::Polycarbonate)).unwrap();
+        assert!(crown.uva_transmittance > 0.5);
+        assert_eq!(poly.uva_transmittance, 0.0);
+        assert_eq!(poly.uvb_transmittance, 0.0);
+        assert_eq!(poly.category, FilterCategory::Category0);
+        assert!(poly.suitable_for_night_driving);
+    }
+
+    #[test]
+    fn sampled_spectrum_is_interpolated_and_clamped() {
+        let curve = sampled(&[(400.0, 0.1), (500.0, 0.3), (700.0, 0.5)]);
+        assert_abs_diff_eq!(curve.transmittance_at(450.0).unwrap(), 0.2, epsilon = EPS);
+        assert_abs_diff_eq!(curve.transmittance_at(300.0).unwrap(), 0.1, epsilon = EPS);
+        assert_abs_diff_eq!(curve.transmittance_at(780.0).unwrap(), 0.5, epsilon = EPS);
+        assert_abs_diff_eq!(curve.transmittance_at(500.0).unwrap(), 0.3, epsilon = EPS);
+    }
+
+    #[test]
+    fn empty_sampled_spectrum_is_none() {
+        let empty = FilterSpectrum::Sampled(Vec::new());
+        assert_eq!(empty.transmittance_at(550.0), None);
+        assert!(luminous_transmittance(&empty).is_none());
+        assert!(assess_filter(&empty, None).is_none());
+    }
+}
@@ -0,0 +1,481 @@
+//! Frame measurements in the boxing system.
+//!
+//! ## Overview
+//! The boxing system describes each lens opening by the smallest rectangle that
+//! encloses it. These measurements drive decentration and blank sizing.
+//!
+//! ## Measurements
+//! - **A**: Horizontal width of the box (eyesize) (mm)
+//! - **B**: Vertical height of the box (mm)
+//! - **DBL**: Distance between lenses (bridge) (mm)
+//! - **ED**: Effective diameter, twice the longest radius from the boxing center (mm)
+//! - **Frame PD**: Distance between the two boxing centers, `A + DBL` (mm)
+//!
+//! ## Frame Traces
+//! A [`FrameTrace`] holds the lens shape as points viewed from the front of the
+//! frame, with `x` toward the observer's right and `y` up. Polar traces use the
+//! same orientation, with angles counter-clockwise from the observer's right, so
+//! 0° is nasal for OD and temporal for OS. The boxing values, circumference and
+//! area are derived from the closed polygon through the points.
+
+use crate::*;
+
+/// Boxing system measurements of a frame.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct FrameMeasurements {
+    /// Horizontal box width, the eyesize (mm).
+    /// Example: 52□18 → `a_mm = 52.0`.
+    pub a_mm: f64,
+
+    /// Vertical box height (mm).
+    pub b_mm: f64,
+
+    /// Distance between lenses, the bridge size (mm).
+    /// Example: 52□18 → `dbl_mm = 18.0`.
+    pub dbl_mm: f64,
+
+    /// Effective diameter (mm).
+    pub ed_mm: f64,
+
+    /// Angle of the effective diameter through the boxing center in degrees, if known.
+    pub ed_axis_deg: Option<f64>,
+}
+
+impl FrameMeasurements {
+    /// Returns the frame PD (distance between boxing centers), `A + DBL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let frame = FrameMeasurements { a_mm: 52.0, b_mm: 38.0, dbl_mm: 18.0, ed_mm: 56.0, ed_axis_deg: None };
+    /// assert_eq!(frame.frame_pd_mm(), 70.0);
+    /// ```
+    pub fn frame_pd_mm(&self) -> f64 {
+        self.a_mm + self.dbl_mm
+    }
+
+    /// Returns the height of the boxing center above the lowest point of the box (mm).
+    pub fn boxing_center_height_mm(&self) -> f64 {
+        self.b_mm / 2.0
+    }
+}
+
+/// A point on a frame trace, viewed from the front of the frame.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct TracePoint {
+    /// Horizontal position, positive toward the observer's right (mm).
+    pub x_mm: f64,
+
+    /// Vertical position, positive up (mm).
+    pub y_mm: f64,
+}
+
+/// Lens shape from a frame tracer.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct FrameTrace {
+    /// Eye the shape belongs to.
+    pub eye: Eye,
+
+    /// Points of the closed shape, in counter-clockwise order.
+    pub points: Vec<TracePoint>,
+}
+
+impl FrameTrace {
+    /// Creates a trace from Cartesian points.
+    ///
+    /// Returns `None` if fewer than three points are given.
+    pub fn from_points(eye: Eye, points: Vec<TracePoint>) -> Option<Self> {
+        if points.len() < 3 {
+            return None;
+        }
+        Some(Self { eye, points })
+    }
+
+    /// Creates a trace from equiangular polar radii starting at 0°.
+    ///
+    /// Returns `None` if fewer than three radii are given.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let trace = FrameTrace::from_radii(Eye::OD, &[25.0, 20.0, 25.0, 20.0]).unwrap();
+    /// assert_eq!(trace.a_mm(), 50.0);
+    /// assert_eq!(trace.b_mm(), 40.0);
+    /// assert!(FrameTrace::from_radii(Eye::OD, &[25.0, 20.0]).is_none());
+    /// ```
+    pub fn from_radii(eye: Eye, radii_mm: &[f64]) -> Option<Self> {
+        let step_deg = 360.0 / radii_mm.len() as f64;
+        let angles_deg: Vec<f64> = (0..radii_mm.len()).map(|i| i as f64 * step_deg).collect();
+        Self::from_polar(eye, radii_mm, &angles_deg)
+    }
+
+    /// Creates a trace from polar radii at the given angles in degrees.
+    ///
+    /// Returns `None` if the slices differ in length or hold fewer than three
+    /// points.
+    pub fn from_polar(eye: Eye, radii_mm: &[f64], angles_deg: &[f64]) -> Option<Self> {
+        if radii_mm.len() != angles_deg.len() {
+            return None;
+        }
+        let points = radii_mm
+            .iter()
+            .zip(angles_deg)
+            .map(|(r, a)| {
+                let (sin, cos) = a.to_radians().sin_cos();
+                TracePoint {
+                    x_mm: r * cos,
+                    y_mm: r * sin,
+                }
+            })
+            .collect();
+        Self::from_points(eye, points)
+    }
+
+    /// Returns `(min x, max x, min y, max y)` of the points.
+    fn bounds(&self) -> (f64, f64, f64, f64) {
+        self.points.iter().fold(
+            (
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+                f64::INFINITY,
+                f64::NEG_INFINITY,
+            ),
+            |(x0, x1, y0, y1), p| {
+                (
+                    x0.min(p.x_mm),
+                    x1.max(p.x_mm),
+                    y0.min(p.y_mm),
+                    y1.max(p.y_mm),
+                )
+            },
+        )
+    }
+
+    /// Returns the boxed horizontal width, A (mm).
+    pub fn a_mm(&self) -> f64 {
+        let (x0, x1, _, _) = self.bounds();
+        x1 - x0
+    }
+
+    /// Returns the boxed vertical height, B (mm).
+    pub fn b_mm(&self) -> f64 {
+        let (_, _, y0, y1) = self.bounds();
+        y1 - y0
+    }
+
+    /// Returns the geometric center of the boxed shape (the boxing center).
+    pub fn geometric_center(&self) -> TracePoint {
+        let (x0, x1, y0, y1) = self.bounds();
+        TracePoint {
+            x_mm: (x0 + x1) / 2.0,
+            y_mm: (y0 + y1) / 2.0,
+        }
+    }
+
+    /// Returns the point farthest from the geometric center, relative to it.
+    fn farthest_from_center(&self) -> (f64, f64) {
+        let center = self.geometric_center();
+        self.points
+            .iter()
+            .map(|p| (p.x_mm - center.x_mm, p.y_mm - center.y_mm))
+            .fold((0.0, 0.0), |best: (f64, f64), p| {
+                if p.0.hypot(p.1) > best.0.hypot(best.1) {
+                    p
+                } else {
+                    best
+                }
+            })
+    }
+
+    /// Returns the effective diameter, twice the longest radius from the geometric center (mm).
+    pub fn ed_mm(&self) -> f64 {
+        let (x, y) = self.farthest_from_center();
+        2.0 * x.hypot(y)
+    }
+
+    /// Returns the angle of the effective diameter through the geometric center,
+    /// in degrees [0, 180).
+    pub fn ed_axis_deg(&self) -> f64 {
+        let (x, y) = self.farthest_from_center();
+        y.atan2(x).to_degrees().rem_euclid(180.0)
+    }
+
+    /// Returns the perimeter of the closed shape (mm).
+    pub fn circumference_mm(&self) -> f64 {
+        self.edges()
+            .map(|(p, q)| (q.x_mm - p.x_mm).hypot(q.y_mm - p.y_mm))
+            .sum()
+    }
+
+    /// Returns the enclosed area (mm²).
+    pub fn area_mm2(&self) -> f64 {
+        let twice: f64 = self
+            .edges()
+            .map(|(p, q)| p.x_mm * q.y_mm - q.x_mm * p.y_mm)
+            .sum();
+        twice.abs() / 2.0
+    }
+
+    /// Iterates over the edges of the closed shape.
+    fn edges(&self) -> impl Iterator<Item = (TracePoint, TracePoint)> + '_ {
+        self.points
+            .iter()
+            .zip(self.points.iter().cycle().skip(1))
+            .map(|(p, q)| (*p, *q))
+    }
+
+    /// Builds boxing measurements from the trace and the frame's bridge.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let trace = FrameTrace::from_radii(Eye::OD, &[25.0, 20.0, 25.0, 20.0]).unwrap();
+    /// let frame = trace.to_frame_measurements(18.0);
+    /// assert_eq!(frame.frame_pd_mm(), 68.0);
+    /// assert_eq!(frame.ed_mm, 50.0);
+    /// ```
+    pub fn to_frame_measurements(&self, dbl_mm: f64) -> FrameMeasurements {
+        FrameMeasurements {
+            a_mm: self.a_mm(),
+            b_mm: self.b_mm(),
+            dbl_mm,
+            ed_mm: self.ed_mm(),
+            ed_axis_deg: Some(self.ed_axis_deg()),
+        }
+    }
+
+    /// Scales the shape about its geometric center to a new circumference.
+    ///
+    /// Edgers size lenses by circumference; this fits the same shape to a
+    /// different eye size.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let trace = FrameTrace::from_radii(Eye::OD, &[25.0, 20.0, 25.0, 20.0]).unwrap();
+    /// let larger = trace.resized_to_circumference(trace.circumference_mm() + 3.0);
+    /// assert_abs_diff_eq!(larger.circumference_mm(), trace.circumference_mm() + 3.0, epsilon = 1e-9);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics in debug builds if `circumference_mm` is not positive.
+    pub fn resized_to_circumference(&self, circumference_mm: f64) -> Self {
+        debug_assert!(circumference_mm > 0.0, "circumference_mm must be positive");
+
+        let scale = circumference_mm / self.circumference_mm();
+        let center = self.geometric_center();
+        let points = self
+            .points
+            .iter()
+            .map(|p| TracePoint {
+                x_mm: center.x_mm + scale * (p.x_mm - center.x_mm),
+                y_mm: center.y_mm + scale * (p.y_mm - center.y_mm),
+            })
+            .collect();
+        Self {
+            eye: self.eye,
+            points,
+        }
+    }
+
+    /// Mirrors the shape for the other eye.
+    ///
+    /// The shape is reflected about its vertical axis so the nasal side stays
+    /// nasal, and the point order is reversed to remain counter-clockwise.
+    pub fn mirrored(&self) -> Self {
+        let eye = match self.eye {
+            Eye::OD => Eye::OS,
+            Eye::OS => Eye::OD,
+        };
+        let points = self
+            .points
+            .iter()
+            .rev()
+            .map(|p| TracePoint {
+                x_mm: -p.x_mm,
+                y_mm: p.y_mm,
+            })
+            .collect();
+        Self { eye, points }
+    }
+
+    /// Returns `count` equiangular radii measured from the geometric center,
+    /// starting at 0°.
+    ///
+    /// Each radius is where a ray from the geometric center leaves the shape.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let trace = FrameTrace::from_radii(Eye::OD, &[25.0, 20.0, 25.0, 20.0]).unwrap();
+    /// let radii = trace.equiangular_radii(8);
+    /// assert_abs_diff_eq!(radii[0], 25.0, epsilon = 1e-9);
+    /// assert_abs_diff_eq!(radii[2], 20.0, epsilon = 1e-9);
+    /// ```
+    pub fn equiangular_radii(&self, count: usize) -> Vec<f64> {
+        let center = self.geometric_center();
+        let step_deg = 360.0 / count as f64;
+        (0..count)
+            .map(|i| {
+                let (dy, dx) = (i as f64 * step_deg).to_radians().sin_cos();
+                self.edges()
+                    .filter_map(|(p, q)| {
+                        let (px, py) = (p.x_mm - center.x_mm, p.y_mm - center.y_mm);
+                        let (ex, ey) = (q.x_mm - p.x_mm, q.y_mm - p.y_mm);
+                        let denom = dx * ey - dy * ex;
+                        if denom.abs() < f64::EPSILON {
+                            return None;
+                        }
+                        let t = (px * ey - py * ex) / denom;
+                        let s = (px * dy - py * dx) / denom;
+                        (t >= 0.0 && (-1e-12..=1.0 + 1e-12).contains(&s)).then_some(t)
+                    })
+                    .fold(0.0, f64::max)
+            })
+            .collect()
+    }
+
+    /// Resamples the shape to `count` equiangular points about its geometric center.
+    ///
+    /// The resampled trace is centered on the geometric center. Returns `None`
+    /// if `count` is less than three.
+    pub fn resampled(&self, count: usize) -> Option<Self> {
+        Self::from_radii(self.eye, &self.equiangular_radii(count))
+    }
+
+    /// Calculate the minimum blank size for this shape with the blank centered
+    /// at `dec` from the geometric center.
+    ///
+    /// `limiting_index` in the result indexes [`points`](FrameTrace::points).
+    /// See [`trace_blank_size`].
+    pub fn blank_size(&self, dec: Decentration, working_edge_mm: f64) -> TraceBlankSize {
+        let center = self.geometric_center();
+        let points = self
+            .points
+            .iter()
+            .map(|p| (p.x_mm - center.x_mm, p.y_mm - center.y_mm));
+        points_blank_size(self.eye, points, dec, working_edge_mm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+    use std::f64::consts::PI;
+
+    const EPS: f64 = 1e-9;
+
+    fn rectangle(eye: Eye) -> FrameTrace {
+        // 50 × 30 mm box centered 5 mm right of and 2 mm above the origin
+        let corners = [(30.0, -13.0), (30.0, 17.0), (-20.0, 17.0), (-20.0, -13.0)];
+        FrameTrace::from_points(
+            eye,
+            corners
+                .iter()
+                .map(|&(x_mm, y_mm)| TracePoint { x_mm, y_mm })
+                .collect(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn rectangle_boxing_values() {
+        let trace = rectangle(Eye::OD);
+        assert_abs_diff_eq!(trace.a_mm(), 50.0, epsilon = EPS);
+        assert_abs_diff_eq!(trace.b_mm(), 30.0, epsilon = EPS);
+        assert_eq!(
+            trace.geometric_center(),
+            TracePoint {
+                x_mm: 5.0,
+                y_mm: 2.0
+            }
+        );
+        assert_abs_diff_eq!(trace.ed_mm(), 50.0f64.hypot(30.0), epsilon = EPS);
+        assert_abs_diff_eq!(trace.circumference_mm(), 160.0, epsilon = EPS);
+        assert_abs_diff_eq!(trace.area_mm2(), 1500.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn circle_values_match_closed_forms() {
+        let trace = FrameTrace::from_radii(Eye::OS, &[25.0; 720]).unwrap();
+        assert_abs_diff_eq!(trace.ed_mm(), 50.0, epsilon = EPS);
+        assert_abs_diff_eq!(trace.circumference_mm(), 50.0 * PI, epsilon = 1e-3);
+        assert_abs_diff_eq!(trace.area_mm2(), 625.0 * PI, epsilon = 0.1);
+    }
+
+    #[test]
+    fn resample_is_centered_on_geometric_center() {
+        let resampled = rectangle(Eye::OD).resampled(4).unwrap();
+        let radii: Vec<f64> = resampled
+            .points
+            .iter()
+            .map(|p| p.x_mm.hypot(p.y_mm))
+            .collect();
+        for (r, expected) in radii.iter().zip([25.0, 15.0, 25.0, 15.0]) {
+            assert_abs_diff_eq!(*r, expected, epsilon = EPS);
+        }
+        assert!(rectangle(Eye::OD).resampled(2).is_none());
+    }
+
+    #[test]
+    fn mirror_swaps_eye_and_preserves_shape() {
+        let od = FrameTrace::from_radii(Eye::OD, &[27.0, 20.0, 24.0, 18.0]).unwrap();
+        let os = od.mirrored();
+        assert_eq!(os.eye, Eye::OS);
+        assert_abs_diff_eq!(os.area_mm2(), od.area_mm2(), epsilon = EPS);
+        // The radius at θ on OD lies at 180° − θ on OS
+        let od_radii = od.equiangular_radii(8);
+        let os_radii = os.equiangular_radii(8);
+        for k in 0..8 {
+            assert_abs_diff_eq!(os_radii[(12 - k) % 8], od_radii[k], epsilon = EPS);
+        }
+        // Still counter-clockwise: signed area is positive
+        let signed: f64 = os
+            .edges()
+            .map(|(p, q)| p.x_mm * q.y_mm - q.x_mm * p.y_mm)
+            .sum();
+        assert!(signed > 0.0);
+    }
+
+    #[test]
+    fn blank_size_matches_radii_for_centered_trace() {
+        // Symmetric box, so the geometric center is the polar origin
+        let radii = [25.0, 24.0, 20.0, 24.0, 25.0, 24.0, 20.0, 24.0];
+        let dec = Decentration {
+            horizontal_mm: 2.5,
+            vertical_mm: -1.0,
+        };
+        let expected = trace_blank_size(Eye::OS, &radii, dec, 2.0).unwrap();
+        let actual = FrameTrace::from_radii(Eye::OS, &radii)
+            .unwrap()
+            .blank_size(dec, 2.0);
+        assert_abs_diff_eq!(
+            actual.minimum_blank_mm,
+            expected.minimum_blank_mm,
+            epsilon = EPS
+        );
+        assert_eq!(actual.limiting_index, expected.limiting_index);
+        assert_abs_diff_eq!(
+            actual.limiting_angle_deg,
+            expected.limiting_angle_deg,
+            epsilon = EPS
+        );
+    }
+}
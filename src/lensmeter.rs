@@ -0,0 +1,225 @@
+//! Focimeter (lensmeter) readings of thick lenses.
+//!
+//! ## Overview
+//! [`LensmeterReading::simulate`] predicts what a focimeter displays for a
+//! finished lens: the vertex power of the surface resting on the lens stop,
+//! scaled for the instrument's calibration index, and the prism at the point
+//! of the lens centered in the instrument.
+//!
+//! ## Formulas
+//! ```text
+//! Back surface:   F₂ from LensForm::back_curve_for(BVP), per principal meridian
+//! Equivalent:     Fe  = F₁ + F₂ − (t/n) F₁ F₂
+//! Back vertex:    BVP = Fe / (1 − (t/n) F₁)
+//! Front vertex:   FVP = Fe / (1 − (t/n) F₂)
+//! Display:        F_read = F_vertex · (n_cal − 1) / (n − 1)
+//! Prism:          Prentice's rule on F_read at the measured point
+//! ```
+//!
+//! ## Conventions
+//! - The lens is described by its true back vertex power and its [`LensForm`].
+//! - The measured point is given relative to the optical center, positive
+//!   in (nasal) and up, as for [`Decentration`].
+//! - A calibration index equal to the lens index gives the true vertex power.
+
+use crate::*;
+
+/// Surface of the lens resting against the focimeter's lens stop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum LensStopSurface {
+    /// Back surface on the stop: reads back vertex power (distance Rx).
+    Back,
+
+    /// Front surface on the stop: reads front vertex power (used for adds).
+    Front,
+}
+
+/// Focimeter settings for a reading.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct LensmeterSetup {
+    /// Index the instrument is calibrated for.
+    ///
+    /// Example: crown glass calibration → `calibration_index = 1.523`
+    pub calibration_index: f64,
+
+    /// Surface on the lens stop.
+    pub surface: LensStopSurface,
+
+    /// Point of the lens centered on the stop, relative to the optical center (mm).
+    ///
+    /// Example: 3 mm below the optical center → `vertical_mm = -3.0`
+    pub measurement_point: Decentration,
+}
+
+/// What a focimeter displays for a lens.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct LensmeterReading {
+    /// Displayed sphere, cylinder and axis.
+    pub lens: SpheroCyl,
+
+    /// Displayed prism at the measured point.
+    pub prism: CombinedPrism,
+}
+
+impl LensmeterReading {
+    /// Simulates a focimeter reading of a thick lens.
+    ///
+    /// `rx` is the true back vertex power of the lens and `form` its physical form.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// // +6.00 D CR-39 lens on an 8 base, read front surface down
+    /// let rx = SpheroCyl { sphere: 6.0, cylinder: 0.0, axis_deg: 0.0 };
+    /// let form = LensForm { front_curve: 8.0, center_thickness_mm: 6.0, index: CR_39_INDEX };
+    /// let setup = LensmeterSetup {
+    ///     calibration_index: CR_39_INDEX,
+    ///     surface: LensStopSurface::Front,
+    ///     measurement_point: Decentration { horizontal_mm: 0.0, vertical_mm: 0.0 },
+    /// };
+    /// let reading = LensmeterReading::simulate(Eye::OD, rx, form, setup);
+    /// assert!(reading.lens.sphere < 6.0); // front vertex power is weaker
+    /// assert_abs_diff_eq!(reading.prism.magnitude(), 0.0, epsilon = 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if `setup.calibration_index` or `form.index` is not greater than 1.
+    pub fn simulate(eye: Eye, rx: SpheroCyl, form: LensForm, setup: LensmeterSetup) -> Self {
+        let reduced_thickness = form.center_thickness_mm / 1000.0 / form.index;
+        let vertex_power = |back_vertex_power: f64| match setup.surface {
+            LensStopSurface::Back => back_vertex_power,
+            LensStopSurface::Front => {
+                let f1 = form.front_curve;
+                let f2 = form.back_curve_for(back_vertex_power);
+                let equivalent = f1 + f2 - reduced_thickness * f1 * f2;
+                equivalent / (1.0 - reduced_thickness * f2)
+            }
+        };
+
+        let meridian_1 = vertex_power(rx.sphere);
+        let meridian_2 = vertex_power(rx.sphere + rx.cylinder);
+        let vertex = SpheroCyl {
+            sphere: meridian_1,
+            cylinder: meridian_2 - meridian_1,
+            axis_deg: rx.axis_deg,
+        };
+        let lens = simulate_lensmeter_reading(vertex, setup.calibration_index, form.index);
+
+        // The optical center sits opposite the measured point
+        let dec = Decentration {
+            horizontal_mm: -setup.measurement_point.horizontal_mm,
+            vertical_mm: -setup.measurement_point.vertical_mm,
+        };
+
+        Self {
+            lens,
+            prism: induced_prism(eye, lens, dec),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn setup(surface: LensStopSurface, horizontal_mm: f64, vertical_mm: f64) -> LensmeterSetup {
+        LensmeterSetup {
+            calibration_index: CR_39_INDEX,
+            surface,
+            measurement_point: Decentration {
+                horizontal_mm,
+                vertical_mm,
+            },
+        }
+    }
+
+    #[test]
+    fn back_vertex_at_center_reads_the_rx() {
+        let rx = SpheroCyl {
+            sphere: -3.25,
+            cylinder: -1.5,
+            axis_deg: 35.0,
+        };
+        let form = LensForm {
+            front_curve: 4.0,
+            center_thickness_mm: 2.0,
+            index: CR_39_INDEX,
+        };
+        let reading =
+            LensmeterReading::simulate(Eye::OS, rx, form, setup(LensStopSurface::Back, 0.0, 0.0));
+        assert_abs_diff_eq!(reading.lens.sphere, -3.25, epsilon = 1e-12);
+        assert_abs_diff_eq!(reading.lens.cylinder, -1.5, epsilon = 1e-12);
+        assert_abs_diff_eq!(reading.lens.axis_deg, 35.0, epsilon = 1e-12);
+    }
+
+    #[test]
+    fn front_vertex_matches_thick_lens_formula() {
+        // F1 = +10, F2 = −4 at t/n = 0.004: Fe = 6.16, FVP = 6.16 / 1.016
+        let form = LensForm {
+            front_curve: 10.0,
+            center_thickness_mm: 6.0,
+            index: 1.5,
+        };
+        let bvp = 6.16 / (1.0 - 0.004 * 10.0);
+        let rx = SpheroCyl {
+            sphere: bvp,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let mut s = setup(LensStopSurface::Front, 0.0, 0.0);
+        s.calibration_index = 1.5;
+        let reading = LensmeterReading::simulate(Eye::OD, rx, form, s);
+        assert_abs_diff_eq!(reading.lens.sphere, 6.16 / 1.016, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn calibration_index_scales_the_display() {
+        let rx = SpheroCyl {
+            sphere: -5.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let form = LensForm {
+            front_curve: 2.0,
+            center_thickness_mm: 1.5,
+            index: POLYCARBONATE_INDEX,
+        };
+        let mut s = setup(LensStopSurface::Back, 0.0, 0.0);
+        s.calibration_index = CROWN_GLASS_INDEX;
+        let reading = LensmeterReading::simulate(Eye::OD, rx, form, s);
+        assert_abs_diff_eq!(reading.lens.sphere, -4.463, epsilon = 1e-3);
+    }
+
+    #[test]
+    fn off_center_point_shows_prentice_prism() {
+        // −4.00 D read 5 mm below the optical center: 2Δ base down
+        let rx = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let form = LensForm {
+            front_curve: 3.0,
+            center_thickness_mm: 2.0,
+            index: CR_39_INDEX,
+        };
+        let reading =
+            LensmeterReading::simulate(Eye::OD, rx, form, setup(LensStopSurface::Back, 0.0, -5.0));
+        assert_abs_diff_eq!(reading.prism.vertical.amount(), 2.0, epsilon = 1e-12);
+        assert_eq!(reading.prism.vertical.base(), Some(VerticalBase::Down));
+        assert!(reading.prism.horizontal.is_none());
+    }
+}
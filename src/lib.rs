@@ -6,6 +6,32 @@ mod oblique_meridian;
 mod prism;
 mod transpose;
 mod materials;
+mod astigmatism;
+mod toric_iol;
+mod alpins;
+mod ablation;
+mod visual_acuity;
+mod accommodation;
+mod frame;
+mod blank_catalog;
+mod spectacle_rx;
+mod position_of_wear;
+mod oblique_power;
+mod chromatic;
+mod reflectance;
+mod filters;
+mod verification;
+mod lensmeter;
+mod power_vector;
+mod rounding;
+mod normalize;
+mod rx_change;
+mod refraction_stats;
+mod anisometropia;
+mod progressive;
+
+#[cfg(feature = "oma")]
+mod oma;
 
 #[cfg(feature = "wasm")]
 pub mod wasm;
@@ -17,6 +43,32 @@ pub use oblique_meridian::*;
 pub use prism::*;
 pub use transpose::*;
 pub use materials::*;
+pub use astigmatism::*;
+pub use toric_iol::*;
+pub use alpins::*;
+pub use ablation::*;
+pub use visual_acuity::*;
+pub use accommodation::*;
+pub use frame::*;
+pub use blank_catalog::*;
+pub use spectacle_rx::*;
+pub use position_of_wear::*;
+pub use oblique_power::*;
+pub use chromatic::*;
+pub use reflectance::*;
+pub use filters::*;
+pub use verification::*;
+pub use lensmeter::*;
+pub use power_vector::*;
+pub use rounding::*;
+pub use normalize::*;
+pub use rx_change::*;
+pub use refraction_stats::*;
+pub use anisometropia::*;
+pub use progressive::*;
+
+#[cfg(feature = "oma")]
+pub use oma::*;
 
 /// Defines the eye.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -407,12 +459,14 @@ pub struct Decentration {
     /// Vertical decentration in millimeters.
     /// - Positive = **up**.
     /// - Negative = **down**.
+    ///
     /// Example: 3 mm down → `vertical_mm = -3.0`.
     pub vertical_mm: f64,
 
     /// Horizontal decentration in millimeters.
     /// - Positive = **in** (nasal).
     /// - Negative = **out** (temporal).
+    ///
     /// Example: 2 mm in → `horizontal_mm = 2.0`.
     pub horizontal_mm: f64,
 }
@@ -8,6 +8,18 @@
 //! All refractive indices are measured at the sodium D-line (589.3 nm) at 20°C,
 //! which is the standard reference wavelength in optical calculations.
 //!
+//! ## Dispersion
+//!
+//! The index at other wavelengths comes from a [`Dispersion`] model. Built-in
+//! materials use a two-term Cauchy fit through their nd and Abbe number, which
+//! reproduces both exactly; Sellmeier coefficients can be supplied where a
+//! manufacturer publishes them.
+//!
+//! ```text
+//! Cauchy:     n(λ) = A + B/λ² + C/λ⁴
+//! Sellmeier:  n²(λ) = 1 + Σ Bᵢλ² / (λ² − Cᵢ)        (λ in µm)
+//! ```
+//!
 //! ## Usage
 //!
 //! ```rust
@@ -81,3 +93,261 @@ pub const HIGH_INDEX_167_INDEX: f64 = 1.670;
 /// 
 /// **Refractive Index:** 1.740 at 589.3 nm (sodium D-line)
 pub const HIGH_INDEX_174_INDEX: f64 = 1.740;
+
+/// Common spectacle lens materials.
+///
+/// Identifies a material in catalogs and per-material lookups; use
+/// [`LensMaterial::index`] where a refractive index is needed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum LensMaterial {
+    /// CR-39 hard resin.
+    Cr39,
+
+    /// Trivex.
+    Trivex,
+
+    /// Polycarbonate.
+    Polycarbonate,
+
+    /// Crown glass.
+    CrownGlass,
+
+    /// High-index 1.60 plastic.
+    HighIndex160,
+
+    /// High-index 1.67 plastic.
+    HighIndex167,
+
+    /// High-index 1.74 plastic.
+    HighIndex174,
+}
+
+impl LensMaterial {
+    /// All materials, in order of increasing refractive index.
+    pub const ALL: [LensMaterial; 7] = [
+        LensMaterial::Cr39,
+        LensMaterial::CrownGlass,
+        LensMaterial::Trivex,
+        LensMaterial::Polycarbonate,
+        LensMaterial::HighIndex160,
+        LensMaterial::HighIndex167,
+        LensMaterial::HighIndex174,
+    ];
+
+    /// Returns the refractive index at the sodium D-line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// assert_eq!(LensMaterial::Polycarbonate.index(), POLYCARBONATE_INDEX);
+    /// ```
+    pub fn index(&self) -> f64 {
+        match self {
+            LensMaterial::Cr39 => CR_39_INDEX,
+            LensMaterial::Trivex => TRIVEX_INDEX,
+            LensMaterial::Polycarbonate => POLYCARBONATE_INDEX,
+            LensMaterial::CrownGlass => CROWN_GLASS_INDEX,
+            LensMaterial::HighIndex160 => HIGH_INDEX_160_INDEX,
+            LensMaterial::HighIndex167 => HIGH_INDEX_167_INDEX,
+            LensMaterial::HighIndex174 => HIGH_INDEX_174_INDEX,
+        }
+    }
+
+    /// Returns the typical Abbe number (constringence, Vd).
+    ///
+    /// Lower values disperse light more and show more color fringing
+    /// away from the optical center.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// assert!(LensMaterial::Trivex.abbe_number() > LensMaterial::Polycarbonate.abbe_number());
+    /// ```
+    pub fn abbe_number(&self) -> f64 {
+        match self {
+            LensMaterial::Cr39 => 58.0,
+            LensMaterial::Trivex => 45.0,
+            LensMaterial::Polycarbonate => 30.0,
+            LensMaterial::CrownGlass => 59.0,
+            LensMaterial::HighIndex160 => 42.0,
+            LensMaterial::HighIndex167 => 32.0,
+            LensMaterial::HighIndex174 => 33.0,
+        }
+    }
+
+    /// Returns the typical UV cutoff wavelength of untreated lenses (nm).
+    ///
+    /// Light below this wavelength is treated as fully absorbed, so a
+    /// cutoff of 380 nm or more blocks UV-A and UV-B.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// assert!(LensMaterial::Polycarbonate.uv_cutoff_nm() >= 380.0);
+    /// assert!(LensMaterial::CrownGlass.uv_cutoff_nm() < 380.0);
+    /// ```
+    pub fn uv_cutoff_nm(&self) -> f64 {
+        match self {
+            LensMaterial::Cr39 => 355.0,
+            LensMaterial::Trivex => 394.0,
+            LensMaterial::Polycarbonate => 385.0,
+            LensMaterial::CrownGlass => 320.0,
+            LensMaterial::HighIndex160 => 390.0,
+            LensMaterial::HighIndex167 => 400.0,
+            LensMaterial::HighIndex174 => 400.0,
+        }
+    }
+}
+
+/// Sodium D-line wavelength (nm), the reference for nd in this crate.
+pub const WAVELENGTH_D_NM: f64 = 589.3;
+
+/// Mercury e-line wavelength (nm), the reference for ne.
+pub const WAVELENGTH_E_NM: f64 = 546.07;
+
+/// Hydrogen F-line wavelength (nm), the blue end of the Abbe number.
+pub const WAVELENGTH_F_NM: f64 = 486.13;
+
+/// Hydrogen C-line wavelength (nm), the red end of the Abbe number.
+pub const WAVELENGTH_C_NM: f64 = 656.27;
+
+/// Model of refractive index as a function of wavelength.
+///
+/// Coefficients use wavelengths in micrometres, as published in glass and
+/// resin catalogs.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum Dispersion {
+    /// Cauchy equation, `n = a + b/λ² + c/λ⁴`.
+    Cauchy {
+        /// Constant term.
+        a: f64,
+        /// λ⁻² coefficient (µm²).
+        b: f64,
+        /// λ⁻⁴ coefficient (µm⁴).
+        c: f64,
+    },
+
+    /// Three-term Sellmeier equation, `n² = 1 + Σ bᵢλ² / (λ² − cᵢ)`.
+    Sellmeier {
+        /// Oscillator strengths.
+        b: [f64; 3],
+        /// Resonance wavelengths squared (µm²).
+        c: [f64; 3],
+    },
+}
+
+impl Dispersion {
+    /// Fits a two-term Cauchy model through nd and the Abbe number Vd.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let poly = Dispersion::from_abbe(1.586, 30.0);
+    /// assert_abs_diff_eq!(poly.index_at(WAVELENGTH_D_NM), 1.586, epsilon = 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics in debug builds if `nd` is not greater than 1 or `abbe_number` is
+    /// not positive.
+    pub fn from_abbe(nd: f64, abbe_number: f64) -> Self {
+        debug_assert!(nd > 1.0, "nd must be > 1.0");
+        debug_assert!(abbe_number > 0.0, "abbe_number must be positive");
+
+        let inv_sq = |nm: f64| (nm / 1000.0).powi(-2);
+        let b = (nd - 1.0) / (abbe_number * (inv_sq(WAVELENGTH_F_NM) - inv_sq(WAVELENGTH_C_NM)));
+        Dispersion::Cauchy {
+            a: nd - b * inv_sq(WAVELENGTH_D_NM),
+            b,
+            c: 0.0,
+        }
+    }
+
+    /// Returns the refractive index at a wavelength (nm).
+    ///
+    /// # Panics
+    /// Panics in debug builds if `wavelength_nm` is not positive.
+    pub fn index_at(&self, wavelength_nm: f64) -> f64 {
+        debug_assert!(wavelength_nm > 0.0, "wavelength_nm must be positive");
+
+        let l2 = (wavelength_nm / 1000.0).powi(2);
+        match *self {
+            Dispersion::Cauchy { a, b, c } => a + b / l2 + c / (l2 * l2),
+            Dispersion::Sellmeier { b, c } => {
+                let sum: f64 = b.iter().zip(c).map(|(bi, ci)| bi * l2 / (l2 - ci)).sum();
+                (1.0 + sum).sqrt()
+            }
+        }
+    }
+
+    /// Returns the Abbe number implied by the model, `(nd − 1) / (nF − nC)`.
+    pub fn abbe_number(&self) -> f64 {
+        (self.index_at(WAVELENGTH_D_NM) - 1.0)
+            / (self.index_at(WAVELENGTH_F_NM) - self.index_at(WAVELENGTH_C_NM))
+    }
+}
+
+impl LensMaterial {
+    /// Returns the dispersion model, fitted through nd and the Abbe number.
+    pub fn dispersion(&self) -> Dispersion {
+        Dispersion::from_abbe(self.index(), self.abbe_number())
+    }
+
+    /// Returns the refractive index at a wavelength (nm).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// // ne is slightly higher than nd for every material
+    /// let ne = LensMaterial::Polycarbonate.index_at(WAVELENGTH_E_NM);
+    /// assert!(ne > POLYCARBONATE_INDEX && ne < 1.595);
+    /// ```
+    pub fn index_at(&self, wavelength_nm: f64) -> f64 {
+        self.dispersion().index_at(wavelength_nm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn cauchy_fit_reproduces_abbe_number() {
+        for material in LensMaterial::ALL {
+            let model = material.dispersion();
+            assert_abs_diff_eq!(model.index_at(WAVELENGTH_D_NM), material.index(), epsilon = 1e-12);
+            assert_abs_diff_eq!(model.abbe_number(), material.abbe_number(), epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn sellmeier_matches_published_bk7() {
+        // Schott N-BK7: nd = 1.5168 at 587.56 nm, ne = 1.5187
+        let bk7 = Dispersion::Sellmeier {
+            b: [1.039_612_12, 0.231_792_344, 1.010_469_45],
+            c: [0.006_000_698_67, 0.020_017_914_4, 103.560_653],
+        };
+        assert_abs_diff_eq!(bk7.index_at(587.56), 1.5168, epsilon = 1e-4);
+        assert_abs_diff_eq!(bk7.index_at(WAVELENGTH_E_NM), 1.5187, epsilon = 1e-4);
+        assert_abs_diff_eq!(bk7.abbe_number(), 64.17, epsilon = 0.1);
+    }
+
+    #[test]
+    fn index_falls_with_wavelength() {
+        let model = LensMaterial::HighIndex167.dispersion();
+        assert!(model.index_at(400.0) > model.index_at(550.0));
+        assert!(model.index_at(550.0) > model.index_at(700.0));
+    }
+}
@@ -22,7 +22,29 @@
 //! - This accounts for edge imperfections and manufacturing tolerances
 //! - All measurements are in millimeters
 //! - This calculation assumes standard single vision lens requirements
+//!
+//! ## Per-Eye Blank Size
+//! [`per_eye_blank_size`] sizes each lens separately from [`FrameMeasurements`],
+//! using the monocular PD, the fitting height and any prism produced by decentration:
+//! ```text
+//! Minimum Blank Size = ED + 2 × |Decentration| + Working Edge
+//! ```
+//! The decentration is the distance from the boxing center to the blank's
+//! geometric center, combining horizontal and vertical components.
+//!
+//! ## Blank Size from a Trace
+//! The effective diameter is a conservative stand-in for the lens shape.
+//! [`trace_blank_size`] uses the tracer's polar radii instead and sizes the blank
+//! from the trace point farthest from the decentered blank center:
+//! ```text
+//! Minimum Blank Size = 2 × max |Trace Point − Blank Center| + Working Edge
+//! ```
+//! For a circular trace of diameter ED this reduces to the per-eye formula above.
 
+use crate::*;
+
+/// Working edge added to the minimum blank size by [`recommended_blank_size`] (mm).
+pub const DEFAULT_WORKING_EDGE_MM: f64 = 2.0;
 
 /// Calculate the minimum blank size for a single vision lens.
 ///
@@ -99,7 +121,243 @@ pub fn recommended_blank_size(
     bridge_mm: f64,
     ipd_mm: f64,
 ) -> f64 {
-    minimum_blank_size(effective_diameter_mm, eyesize_mm, bridge_mm, ipd_mm) + 2.0
+    minimum_blank_size(effective_diameter_mm, eyesize_mm, bridge_mm, ipd_mm)
+        + DEFAULT_WORKING_EDGE_MM
+}
+
+/// Centration of one lens in the frame, used for per-eye blank sizing.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct EyeCentration {
+    /// Lens power, used to compute prism decentration.
+    pub lens: SpheroCyl,
+
+    /// Monocular distance PD, from the frame's bridge center to the pupil (mm).
+    pub monocular_pd_mm: f64,
+
+    /// Height of the fitting point (OC, segment top or PAL fitting cross) above
+    /// the lowest point of the box (mm). `None` places it at the boxing center.
+    pub fitting_height_mm: Option<f64>,
+
+    /// Vertical offset from the fitting point to the blank's geometric center (mm).
+    /// - Positive = blank center **above** the fitting point.
+    /// - Negative = blank center **below** the fitting point.
+    ///
+    /// Example: flat-top segment 5 mm below the blank center → `5.0`;
+    /// PAL fitting cross 4 mm above the blank center → `-4.0`.
+    pub blank_center_offset_mm: f64,
+
+    /// Prescribed prism to be produced by decentering the lens, if any.
+    pub prism: Option<CombinedPrism>,
+}
+
+/// Blank size requirement of one lens.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct EyeBlankSize {
+    /// Position of the blank's geometric center relative to the boxing center.
+    /// Horizontal positive = **in** (nasal), vertical positive = **up**.
+    pub decentration: Decentration,
+
+    /// Decentration added to produce the prescribed prism.
+    /// `None` if no prism was requested, or if the lens has no power to decenter
+    /// in which case the prism must be ground and is ignored here.
+    pub prism_decentration: Option<Decentration>,
+
+    /// Minimum blank diameter including the working edge (mm).
+    pub minimum_blank_mm: f64,
+}
+
+/// Blank size requirements for a pair of lenses.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PerEyeBlankSize {
+    /// Right lens.
+    pub od: EyeBlankSize,
+    /// Left lens.
+    pub os: EyeBlankSize,
+}
+
+fn eye_blank_size(
+    eye: Eye,
+    frame: FrameMeasurements,
+    centration: EyeCentration,
+    working_edge_mm: f64,
+) -> EyeBlankSize {
+    // Pupil relative to the boxing center, positive = nasal
+    let pupil_in_mm = frame.frame_pd_mm() / 2.0 - centration.monocular_pd_mm;
+    let fitting_up_mm = centration
+        .fitting_height_mm
+        .map_or(0.0, |h| h - frame.boxing_center_height_mm());
+
+    let prism_decentration = centration
+        .prism
+        .and_then(|prism| decentration_for_prism(eye, centration.lens, prism));
+    let (prism_in_mm, prism_up_mm) =
+        prism_decentration.map_or((0.0, 0.0), |d| (d.horizontal_mm, d.vertical_mm));
+
+    let decentration = Decentration {
+        vertical_mm: fitting_up_mm + centration.blank_center_offset_mm + prism_up_mm,
+        horizontal_mm: pupil_in_mm + prism_in_mm,
+    };
+    let total_mm = decentration.horizontal_mm.hypot(decentration.vertical_mm);
+
+    EyeBlankSize {
+        decentration,
+        prism_decentration,
+        minimum_blank_mm: frame.ed_mm + 2.0 * total_mm + working_edge_mm,
+    }
+}
+
+/// Calculate the minimum blank size of each lens separately.
+///
+/// Unlike [`minimum_blank_size`], this uses monocular PDs and accounts for
+/// vertical decentration from the fitting height, segment or PAL reference
+/// offsets, and prism produced by decentration. Pass [`DEFAULT_WORKING_EDGE_MM`]
+/// as `working_edge_mm` for the usual 2 mm allowance.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let frame = FrameMeasurements { a_mm: 50.0, b_mm: 40.0, dbl_mm: 15.0, ed_mm: 55.0, ed_axis_deg: None };
+/// let lens = SpheroCyl { sphere: -2.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let eye = |pd| EyeCentration {
+///     lens,
+///     monocular_pd_mm: pd,
+///     fitting_height_mm: None,
+///     blank_center_offset_mm: 0.0,
+///     prism: None,
+/// };
+///
+/// let sizes = per_eye_blank_size(frame, eye(31.0), eye(29.0), DEFAULT_WORKING_EDGE_MM);
+/// assert_abs_diff_eq!(sizes.od.minimum_blank_mm, 55.0 + 2.0 * 1.5 + 2.0, epsilon = 1e-9);
+/// assert_abs_diff_eq!(sizes.os.minimum_blank_mm, 55.0 + 2.0 * 3.5 + 2.0, epsilon = 1e-9);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `working_edge_mm` is negative.
+pub fn per_eye_blank_size(
+    frame: FrameMeasurements,
+    od: EyeCentration,
+    os: EyeCentration,
+    working_edge_mm: f64,
+) -> PerEyeBlankSize {
+    debug_assert!(working_edge_mm >= 0.0, "working_edge_mm must be non-negative");
+
+    PerEyeBlankSize {
+        od: eye_blank_size(Eye::OD, frame, od, working_edge_mm),
+        os: eye_blank_size(Eye::OS, frame, os, working_edge_mm),
+    }
+}
+
+/// Blank size computed from a frame trace.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct TraceBlankSize {
+    /// Minimum blank diameter including the working edge (mm).
+    pub minimum_blank_mm: f64,
+
+    /// Index into the trace radii of the point farthest from the blank center.
+    pub limiting_index: usize,
+
+    /// Trace angle of the limiting point in degrees.
+    pub limiting_angle_deg: f64,
+
+    /// Distance from the blank center to the limiting point (mm).
+    pub limiting_distance_mm: f64,
+}
+
+/// Calculate the minimum blank size from a frame trace and a decentration.
+///
+/// `radii_mm` are equiangular polar radii measured from the boxing center,
+/// starting at 0° and proceeding counter-clockwise as viewed from the front of
+/// the frame, with 0° pointing to the observer's right (nasal for OD, temporal
+/// for OS). `dec` is the position of the blank's geometric center relative to
+/// the boxing center, e.g. [`EyeBlankSize::decentration`].
+///
+/// Returns `None` if `radii_mm` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // 50 × 40 mm rectangle-ish trace sampled every 90°
+/// let radii = [25.0, 20.0, 25.0, 20.0];
+/// let dec = Decentration { horizontal_mm: 3.0, vertical_mm: 0.0 };
+///
+/// let blank = trace_blank_size(Eye::OD, &radii, dec, DEFAULT_WORKING_EDGE_MM).unwrap();
+/// // The temporal point at 180° is 28 mm from the decentered center
+/// assert_eq!(blank.limiting_index, 2);
+/// assert_abs_diff_eq!(blank.minimum_blank_mm, 58.0, epsilon = 1e-9);
+/// ```
+pub fn trace_blank_size(
+    eye: Eye,
+    radii_mm: &[f64],
+    dec: Decentration,
+    working_edge_mm: f64,
+) -> Option<TraceBlankSize> {
+    if radii_mm.is_empty() {
+        return None;
+    }
+
+    let step_deg = 360.0 / radii_mm.len() as f64;
+    let points = radii_mm.iter().enumerate().map(|(i, r)| {
+        let (sin, cos) = (i as f64 * step_deg).to_radians().sin_cos();
+        (r * cos, r * sin)
+    });
+    Some(points_blank_size(eye, points, dec, working_edge_mm))
+}
+
+/// Blank size for trace points `(x, y)` relative to the boxing center, in the
+/// front-view convention of [`trace_blank_size`].
+pub(crate) fn points_blank_size(
+    eye: Eye,
+    points: impl IntoIterator<Item = (f64, f64)>,
+    dec: Decentration,
+    working_edge_mm: f64,
+) -> TraceBlankSize {
+    // Nasal is +x for OD and −x for OS when viewed from the front
+    let center_x = match eye {
+        Eye::OD => dec.horizontal_mm,
+        Eye::OS => -dec.horizontal_mm,
+    };
+    let center_y = dec.vertical_mm;
+
+    let mut limiting_index = 0;
+    let mut limiting_point = (0.0, 0.0);
+    let mut limiting_distance_mm = f64::NEG_INFINITY;
+    for (i, (x, y)) in points.into_iter().enumerate() {
+        let distance = (x - center_x).hypot(y - center_y);
+        if distance > limiting_distance_mm {
+            limiting_index = i;
+            limiting_point = (x, y);
+            limiting_distance_mm = distance;
+        }
+    }
+
+    TraceBlankSize {
+        minimum_blank_mm: 2.0 * limiting_distance_mm + working_edge_mm,
+        limiting_index,
+        limiting_angle_deg: limiting_point
+            .1
+            .atan2(limiting_point.0)
+            .to_degrees()
+            .rem_euclid(360.0),
+        limiting_distance_mm,
+    }
 }
 
 #[cfg(test)]
@@ -228,4 +486,116 @@ mod tests {
         let result = minimum_blank_size(55.0, 0.0, 0.0, 53.0);
         assert_abs_diff_eq!(result, 2.0, epsilon = EPS); // 55 + (0 + 0 - 53) = 2
     }
+
+    fn frame() -> FrameMeasurements {
+        FrameMeasurements {
+            a_mm: 50.0,
+            b_mm: 40.0,
+            dbl_mm: 15.0,
+            ed_mm: 55.0,
+            ed_axis_deg: Some(30.0),
+        }
+    }
+
+    fn centration(monocular_pd_mm: f64) -> EyeCentration {
+        EyeCentration {
+            lens: SpheroCyl {
+                sphere: 4.0,
+                cylinder: 0.0,
+                axis_deg: 0.0,
+            },
+            monocular_pd_mm,
+            fitting_height_mm: None,
+            blank_center_offset_mm: 0.0,
+            prism: None,
+        }
+    }
+
+    #[test]
+    fn per_eye_matches_binocular_formula_for_symmetric_pd() {
+        let sizes = per_eye_blank_size(frame(), centration(26.5), centration(26.5), 0.0);
+        let binocular = minimum_blank_size(55.0, 50.0, 15.0, 53.0);
+        assert_abs_diff_eq!(sizes.od.minimum_blank_mm, binocular, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.os.minimum_blank_mm, binocular, epsilon = EPS);
+    }
+
+    #[test]
+    fn per_eye_includes_vertical_and_segment_offsets() {
+        // Seg top at 18 mm in a 40 mm box, blank center 5 mm above → 3 mm up
+        let od = EyeCentration {
+            fitting_height_mm: Some(18.0),
+            blank_center_offset_mm: 5.0,
+            ..centration(28.5)
+        };
+        let sizes = per_eye_blank_size(frame(), od, centration(28.5), DEFAULT_WORKING_EDGE_MM);
+        // 4 mm in, 3 mm up → 5 mm total
+        assert_abs_diff_eq!(sizes.od.decentration.horizontal_mm, 4.0, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.od.decentration.vertical_mm, 3.0, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.od.minimum_blank_mm, 55.0 + 10.0 + 2.0, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.os.minimum_blank_mm, 55.0 + 8.0 + 2.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn per_eye_adds_prism_decentration() {
+        // +4.00 DS, 1Δ base out needs 2.5 mm out-decentration
+        let od = EyeCentration {
+            prism: Some(CombinedPrism {
+                horizontal: HorizontalPrism::new(1.0, HorizontalBase::Out),
+                vertical: VerticalPrism::new(0.0, VerticalBase::Up),
+            }),
+            ..centration(35.0)
+        };
+        let sizes = per_eye_blank_size(frame(), od, centration(35.0), 0.0);
+        let prism_dec = sizes.od.prism_decentration.unwrap();
+        assert_abs_diff_eq!(prism_dec.horizontal_mm, -2.5, epsilon = EPS);
+        // 2.5 mm out for PD, plus 2.5 mm out for prism
+        assert_abs_diff_eq!(sizes.od.decentration.horizontal_mm, -5.0, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.od.minimum_blank_mm, 65.0, epsilon = EPS);
+        assert_abs_diff_eq!(sizes.os.minimum_blank_mm, 60.0, epsilon = EPS);
+        assert!(sizes.os.prism_decentration.is_none());
+    }
+
+    #[test]
+    fn trace_blank_size_matches_ed_formula_for_circle() {
+        let radii = vec![27.5; 360];
+        let dec = Decentration {
+            horizontal_mm: 3.0,
+            vertical_mm: 4.0,
+        };
+        let blank = trace_blank_size(Eye::OD, &radii, dec, DEFAULT_WORKING_EDGE_MM).unwrap();
+        assert_abs_diff_eq!(blank.minimum_blank_mm, 55.0 + 10.0 + 2.0, epsilon = 1e-3);
+        // Limiting point lies opposite the decentration: temporal and down
+        assert_abs_diff_eq!(
+            blank.limiting_angle_deg,
+            180.0 + 4f64.atan2(3.0).to_degrees(),
+            epsilon = 1.0
+        );
+    }
+
+    #[test]
+    fn trace_blank_size_mirrors_for_left_eye() {
+        // Longer radius on the observer's right side
+        let radii = [30.0, 20.0, 25.0, 20.0];
+        let dec = Decentration {
+            horizontal_mm: 2.0,
+            vertical_mm: 0.0,
+        };
+        // OD: center moves toward the long side → 0° point is 28 mm away
+        let od = trace_blank_size(Eye::OD, &radii, dec, 0.0).unwrap();
+        assert_eq!(od.limiting_index, 0);
+        assert_abs_diff_eq!(od.minimum_blank_mm, 56.0, epsilon = EPS);
+        // OS: center moves away from the long side → 0° point is 32 mm away
+        let os = trace_blank_size(Eye::OS, &radii, dec, 0.0).unwrap();
+        assert_eq!(os.limiting_index, 0);
+        assert_abs_diff_eq!(os.minimum_blank_mm, 64.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn trace_blank_size_is_none_for_empty_trace() {
+        let dec = Decentration {
+            horizontal_mm: 0.0,
+            vertical_mm: 0.0,
+        };
+        assert!(trace_blank_size(Eye::OD, &[], dec, 0.0).is_none());
+    }
 }
@@ -0,0 +1,288 @@
+//! Canonical forms and equivalence of sphero-cylinders.
+//!
+//! ## Overview
+//! The same lens can be written in several ways: `−1.00 −1.00 × 0` and
+//! `−2.00 +1.00 × 90` are optically identical, and an axis of −5° is the
+//! same as 175°. This module provides:
+//! - [`SpheroCyl::normalized`]: minus- or plus-cylinder form with the axis in (0, 180].
+//! - [`SpheroCyl::approx_eq`]: optical equivalence within a dioptric tolerance,
+//!   compared through the power matrix.
+//! - [`SpheroCyl::canonical_key`]: a hashable key for deduplicating orders.
+//!
+//! `SpheroCyl`'s `PartialEq` compares canonical keys, so equal lenses compare
+//! equal whichever way they are written.
+//!
+//! ## Conventions
+//! - A lens without cylinder keeps its axis in `normalized`, but its
+//!   canonical key uses 180°.
+//! - Canonical keys are minus-cylinder form with powers in 0.001 D and the
+//!   axis in 0.1°.
+
+use crate::*;
+
+/// Sign convention for the cylinder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum CylinderForm {
+    /// Negative cylinder, as written by most refractionists.
+    Minus,
+
+    /// Positive cylinder.
+    Plus,
+}
+
+/// Hashable canonical form of a [`SpheroCyl`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SpheroCylKey {
+    /// Sphere in minus-cylinder form (0.001 D).
+    pub sphere_millidiopters: i32,
+
+    /// Cylinder in minus-cylinder form (0.001 D), zero or negative.
+    pub cylinder_millidiopters: i32,
+
+    /// Axis in (0, 180] (0.1°); 1800 when there is no cylinder.
+    pub axis_tenth_deg: i32,
+}
+
+impl SpheroCyl {
+    /// Returns the lens in the given cylinder form with the axis in (0, 180].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let lens = SpheroCyl { sphere: -2.0, cylinder: 1.0, axis_deg: -5.0 };
+    /// let minus = lens.normalized(CylinderForm::Minus);
+    /// assert_eq!(minus.sphere, -1.0);
+    /// assert_eq!(minus.cylinder, -1.0);
+    /// assert_eq!(minus.axis_deg, 85.0);
+    /// ```
+    pub fn normalized(&self, form: CylinderForm) -> SpheroCyl {
+        let wrong_sign = match form {
+            CylinderForm::Minus => self.cylinder > 0.0,
+            CylinderForm::Plus => self.cylinder < 0.0,
+        };
+        let axis_deg = normalize_axis_deg(self.axis_deg);
+        let lens = SpheroCyl { axis_deg, ..*self };
+        let lens = if wrong_sign { lens.transpose() } else { lens };
+        SpheroCyl {
+            axis_deg: normalize_axis_deg(lens.axis_deg),
+            ..lens
+        }
+    }
+
+    /// Returns `true` if both lenses have the same power in every meridian,
+    /// within `tolerance` diopters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let a = SpheroCyl { sphere: -1.0, cylinder: -1.0, axis_deg: 0.0 };
+    /// let b = SpheroCyl { sphere: -2.0, cylinder: 1.0, axis_deg: 90.0 };
+    /// assert!(a.approx_eq(&b, 1e-9));
+    /// ```
+    pub fn approx_eq(&self, other: &SpheroCyl, tolerance: f64) -> bool {
+        let a = power_matrix(*self);
+        let b = power_matrix(*other);
+        a.iter().zip(b).all(|(x, y)| (x - y).abs() <= tolerance)
+    }
+
+    /// Returns the canonical key used for equality and deduplication.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use std::collections::HashSet;
+    ///
+    /// let orders = [
+    ///     SpheroCyl { sphere: -1.0, cylinder: -1.0, axis_deg: 180.0 },
+    ///     SpheroCyl { sphere: -2.0, cylinder: 1.0, axis_deg: 90.0 },
+    ///     SpheroCyl { sphere: -1.0, cylinder: -1.0, axis_deg: 0.0 },
+    /// ];
+    /// let unique: HashSet<_> = orders.iter().map(|rx| rx.canonical_key()).collect();
+    /// assert_eq!(unique.len(), 1);
+    /// ```
+    pub fn canonical_key(&self) -> SpheroCylKey {
+        let lens = self.normalized(CylinderForm::Minus);
+        let cylinder_millidiopters = (lens.cylinder * 1000.0).round() as i32;
+        let axis_tenth_deg = match (lens.axis_deg * 10.0).round() as i32 {
+            _ if cylinder_millidiopters == 0 => 1800,
+            0 => 1800,
+            axis => axis,
+        };
+        SpheroCylKey {
+            sphere_millidiopters: (lens.sphere * 1000.0).round() as i32,
+            cylinder_millidiopters,
+            axis_tenth_deg,
+        }
+    }
+}
+
+impl PartialEq for SpheroCyl {
+    /// Lenses are equal if their canonical keys are equal, i.e. they agree in
+    /// minus-cylinder form after rounding sphere and cylinder to 0.001 D and
+    /// the axis to 0.1°.
+    ///
+    /// Values on either side of a rounding edge compare unequal even if they
+    /// are closer than the quantum, e.g. −1.0004 and −1.0006 D. A lens with a
+    /// NaN field is unequal to every lens, itself included. Use
+    /// [`SpheroCyl::approx_eq`] to compare with a tolerance.
+    fn eq(&self, other: &Self) -> bool {
+        let is_nan = |rx: &Self| rx.sphere.is_nan() || rx.cylinder.is_nan() || rx.axis_deg.is_nan();
+        !is_nan(self) && !is_nan(other) && self.canonical_key() == other.canonical_key()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn normalizes_out_of_range_axes() {
+        let lens = SpheroCyl {
+            sphere: 0.5,
+            cylinder: -0.75,
+            axis_deg: 365.0,
+        }
+        .normalized(CylinderForm::Minus);
+        assert_eq!(lens.axis_deg, 5.0);
+        let lens = SpheroCyl {
+            sphere: 0.5,
+            cylinder: -0.75,
+            axis_deg: 0.0,
+        }
+        .normalized(CylinderForm::Minus);
+        assert_eq!(lens.axis_deg, 180.0);
+        let lens = SpheroCyl {
+            sphere: 0.5,
+            cylinder: -0.75,
+            axis_deg: 90.0,
+        }
+        .normalized(CylinderForm::Plus);
+        assert_eq!(
+            (lens.sphere, lens.cylinder, lens.axis_deg),
+            (-0.25, 0.75, 180.0)
+        );
+    }
+
+    #[test]
+    fn equality_ignores_notation() {
+        let minus = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -1.0,
+            axis_deg: 180.0,
+        };
+        let plus = SpheroCyl {
+            sphere: -2.0,
+            cylinder: 1.0,
+            axis_deg: 90.0,
+        };
+        assert_eq!(
+            SpheroCyl {
+                axis_deg: 0.0,
+                ..minus
+            },
+            plus
+        );
+        assert_eq!(
+            minus,
+            SpheroCyl {
+                axis_deg: 0.0,
+                ..minus
+            }
+        );
+        let sphere = SpheroCyl {
+            sphere: 3.0,
+            cylinder: 0.0,
+            axis_deg: 45.0,
+        };
+        assert_eq!(
+            sphere,
+            SpheroCyl {
+                axis_deg: 120.0,
+                ..sphere
+            }
+        );
+        assert_ne!(
+            minus,
+            SpheroCyl {
+                axis_deg: 90.0,
+                ..minus
+            }
+        );
+    }
+
+    #[test]
+    fn nan_lens_is_never_equal() {
+        let plano = SpheroCyl {
+            sphere: 0.0,
+            cylinder: 0.0,
+            axis_deg: 180.0,
+        };
+        let nan = SpheroCyl {
+            sphere: f64::NAN,
+            ..plano
+        };
+        assert_ne!(nan, plano);
+        assert_ne!(plano, nan);
+        assert_ne!(nan, nan);
+        assert_ne!(
+            SpheroCyl {
+                axis_deg: f64::NAN,
+                cylinder: -1.0,
+                ..plano
+            },
+            SpheroCyl {
+                cylinder: -1.0,
+                ..plano
+            }
+        );
+    }
+
+    #[test]
+    fn approx_eq_uses_the_power_matrix() {
+        // A tiny axis difference on a weak cylinder is a tiny power difference
+        let a = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -0.25,
+            axis_deg: 10.0,
+        };
+        let b = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -0.25,
+            axis_deg: 12.0,
+        };
+        assert!(a.approx_eq(&b, 0.01));
+        let c = SpheroCyl {
+            cylinder: -2.0,
+            ..a
+        };
+        assert!(!c.approx_eq(
+            &SpheroCyl {
+                axis_deg: 20.0,
+                ..c
+            },
+            0.25
+        ));
+    }
+
+    #[test]
+    fn canonical_key_rounds_to_thousandths() {
+        let key = SpheroCyl {
+            sphere: -2.3712,
+            cylinder: -0.8831,
+            axis_deg: 37.44,
+        }
+        .canonical_key();
+        assert_eq!(key.sphere_millidiopters, -2371);
+        assert_eq!(key.cylinder_millidiopters, -883);
+        assert_eq!(key.axis_tenth_deg, 374);
+    }
+}
@@ -0,0 +1,457 @@
+//! Off-axis power errors of spectacle lenses by ray tracing.
+//!
+//! ## Overview
+//! When the eye rotates behind a lens, it looks through the lens obliquely and
+//! receives a different power from the one on axis. [`oblique_power`] traces
+//! the chief ray through the center of rotation of the eye and applies
+//! Coddington's equations along it to find the tangential and sagittal powers
+//! at the vertex sphere. Comparing them with the Rx gives the oblique
+//! astigmatism and mean oblique error used to choose a base curve.
+//! [`oblique_power_with_back_surface`] does the same for a lens whose back
+//! curve is known, such as a measured lens.
+//!
+//! ## Method
+//! Coddington's equations at a surface of curvature `c`, from index `n` to `n'`:
+//! ```text
+//! Oblique power  Φ = (n' cos i' − n cos i) × c
+//! Sagittal       n' / s'          = n / s          + Φ
+//! Tangential     n' cos² i' / t'  = n cos² i / t   + Φ
+//! ```
+//! Vergences are transferred between surfaces along the chief ray, and finally
+//! to the vertex sphere: the sphere centered on the center of rotation that
+//! passes through the back vertex.
+//!
+//! ```text
+//! Oblique astigmatism = (T − T₀) − (S − S₀)
+//! Mean oblique error  = ((T − T₀) + (S − S₀)) / 2
+//! ```
+//! where T₀ and S₀ are the Rx powers in the tangential and sagittal meridians.
+//!
+//! ## Conventions
+//! - The front surface is spherical (the base curve); the back surface carries
+//!   the cylinder, with its curvature along each meridian from Euler's formula.
+//! - Gaze is described by the meridian it moves along and the rotation angle
+//!   from straight ahead, both in degrees.
+//! - Objects are at infinity.
+
+use crate::*;
+
+/// Typical distance from the back vertex of a spectacle lens to the center of rotation of the eye (mm).
+pub const DEFAULT_CENTER_OF_ROTATION_MM: f64 = 27.0;
+
+/// Physical form of a spectacle lens, apart from its back surface.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct LensForm {
+    /// Front surface power, the base curve, at the lens index (D).
+    /// Example: 6 base → `front_curve = 6.0`.
+    pub front_curve: f64,
+
+    /// Center thickness (mm).
+    pub center_thickness_mm: f64,
+
+    /// Refractive index of the lens material.
+    pub index: f64,
+}
+
+impl LensForm {
+    /// Returns the back surface power that gives `back_vertex_power` with this form (D).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let form = LensForm { front_curve: 4.0, center_thickness_mm: 2.0, index: 1.5 };
+    /// // Thin lens: −4.00 D on a 4 base needs about a −8.00 back curve
+    /// assert_abs_diff_eq!(form.back_curve_for(-4.0), -8.021, epsilon = 1e-3);
+    /// ```
+    pub fn back_curve_for(&self, back_vertex_power: f64) -> f64 {
+        let reduced_thickness = self.center_thickness_mm / 1000.0 / self.index;
+        back_vertex_power - self.front_curve / (1.0 - reduced_thickness * self.front_curve)
+    }
+}
+
+/// Powers and errors for one direction of gaze.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ObliquePower {
+    /// Rotation of the eye from straight ahead (degrees).
+    pub gaze_deg: f64,
+
+    /// Power in the meridian of gaze, at the vertex sphere (D).
+    pub tangential: f64,
+
+    /// Power perpendicular to the meridian of gaze, at the vertex sphere (D).
+    pub sagittal: f64,
+
+    /// Tangential minus sagittal error (D).
+    pub oblique_astigmatism: f64,
+
+    /// Mean of the tangential and sagittal errors (D).
+    pub mean_oblique_error: f64,
+}
+
+/// A point or direction in the meridional plane: `(z, y)` with `z` along the
+/// optical axis toward the eye.
+type Vec2 = (f64, f64);
+
+fn dot(a: Vec2, b: Vec2) -> f64 {
+    a.0 * b.0 + a.1 * b.1
+}
+
+/// Intersects a ray with a surface of curvature `c` (1/m) whose vertex is at
+/// `vertex_z`, returning the point and the unit normal facing the ray.
+fn intersect(origin: Vec2, dir: Vec2, vertex_z: f64, c: f64) -> (Vec2, Vec2) {
+    // c (y² + (z − z₀)²) − 2 (z − z₀) = 0, in the root that stays finite as c → 0
+    let w = (origin.0 - vertex_z, origin.1);
+    let b = c * dot(w, dir) - dir.0;
+    let c0 = c * dot(w, w) - 2.0 * w.0;
+    let lambda = -c0 / (b + b.signum() * (b * b - c * c0).max(0.0).sqrt());
+    let point = (origin.0 + lambda * dir.0, origin.1 + lambda * dir.1);
+
+    let normal = (c * (point.0 - vertex_z) - 1.0, c * point.1);
+    let length = normal.0.hypot(normal.1);
+    let mut normal = (normal.0 / length, normal.1 / length);
+    if dot(normal, dir) > 0.0 {
+        normal = (-normal.0, -normal.1);
+    }
+    (point, normal)
+}
+
+/// Refracts a unit direction at a surface with unit normal facing the ray,
+/// returning the new direction and the cosines of incidence and refraction.
+fn refract(dir: Vec2, normal: Vec2, n1: f64, n2: f64) -> (Vec2, f64, f64) {
+    let eta = n1 / n2;
+    let cos_i = -dot(normal, dir);
+    let cos_t = (1.0 - eta * eta * (1.0 - cos_i * cos_i)).sqrt();
+    let k = eta * cos_i - cos_t;
+    (
+        (eta * dir.0 + k * normal.0, eta * dir.1 + k * normal.1),
+        cos_i,
+        cos_t,
+    )
+}
+
+/// Transfers a vergence `v` (D) across distance `d` (m) in a medium of index `n`.
+fn transfer(v: f64, d: f64, n: f64) -> f64 {
+    v / (1.0 - d * v / n)
+}
+
+/// Traces the chief ray and returns the tangential and sagittal powers at the vertex sphere.
+fn trace(
+    form: LensForm,
+    back_tangential: f64,
+    back_sagittal: f64,
+    center_of_rotation_mm: f64,
+    gaze_deg: f64,
+) -> (f64, f64) {
+    let n = form.index;
+    let thickness = form.center_thickness_mm / 1000.0;
+    let cor = center_of_rotation_mm / 1000.0;
+    let c1 = form.front_curve / (n - 1.0);
+    let c2_t = back_tangential / (1.0 - n);
+    let c2_s = back_sagittal / (1.0 - n);
+
+    // Find the chief ray by tracing backward from the center of rotation
+    let center = (thickness + cor, 0.0);
+    let (sin_g, cos_g) = gaze_deg.abs().to_radians().sin_cos();
+    let (q2, normal2) = intersect(center, (-cos_g, sin_g), thickness, c2_t);
+    let (inside, cos_i2_air, cos_i2_glass) = refract((-cos_g, sin_g), normal2, 1.0, n);
+    let (q1, normal1) = intersect(q2, inside, 0.0, c1);
+    let (_, cos_i1_glass, cos_i1_air) = refract(inside, normal1, n, 1.0);
+
+    // Front surface, object at infinity
+    let power1 = (n * cos_i1_glass - cos_i1_air) * c1;
+    let sagittal1 = power1;
+    let tangential1 = power1 / (cos_i1_glass * cos_i1_glass);
+
+    // Along the ray to the back surface
+    let path = (q2.0 - q1.0).hypot(q2.1 - q1.1);
+    let sagittal2 = transfer(sagittal1, path, n);
+    let tangential2 = transfer(tangential1, path, n);
+
+    // Back surface
+    let oblique = cos_i2_air - n * cos_i2_glass;
+    let sagittal_out = oblique * c2_s + sagittal2;
+    let tangential_out =
+        (oblique * c2_t + tangential2 * cos_i2_glass * cos_i2_glass) / (cos_i2_air * cos_i2_air);
+
+    // On to the vertex sphere
+    let to_vertex_sphere = (center.0 - q2.0).hypot(center.1 - q2.1) - cor;
+    (
+        transfer(tangential_out, to_vertex_sphere, 1.0),
+        transfer(sagittal_out, to_vertex_sphere, 1.0),
+    )
+}
+
+/// Compute the off-axis powers of a lens made to `rx` in `form` for one gaze direction.
+///
+/// The eye rotates `gaze_deg` away from straight ahead along `gaze_meridian_deg`
+/// (0° = horizontal, 90° = vertical). The back surface is chosen so that the
+/// lens has the back vertex power of `rx` on axis.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let rx = SpheroCyl { sphere: -4.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let flat = LensForm { front_curve: 0.5, center_thickness_mm: 2.0, index: CR_39_INDEX };
+/// let curved = LensForm { front_curve: 6.0, ..flat };
+///
+/// let flat_30 = oblique_power(rx, flat, DEFAULT_CENTER_OF_ROTATION_MM, 0.0, 30.0);
+/// let curved_30 = oblique_power(rx, curved, DEFAULT_CENTER_OF_ROTATION_MM, 0.0, 30.0);
+/// assert!(curved_30.oblique_astigmatism.abs() < flat_30.oblique_astigmatism.abs());
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `center_of_rotation_mm` is not positive.
+pub fn oblique_power(
+    rx: SpheroCyl,
+    form: LensForm,
+    center_of_rotation_mm: f64,
+    gaze_meridian_deg: f64,
+    gaze_deg: f64,
+) -> ObliquePower {
+    let back_surface = SpheroCyl {
+        sphere: form.back_curve_for(rx.sphere),
+        ..rx
+    };
+    oblique_power_with_back_surface(
+        rx,
+        form,
+        back_surface,
+        center_of_rotation_mm,
+        gaze_meridian_deg,
+        gaze_deg,
+    )
+}
+
+/// Compute the off-axis powers of a lens with a known back surface for one gaze direction.
+///
+/// Use this for a measured lens, or one not surfaced to the exact Rx. The
+/// back surface power along each meridian follows `back_surface` as a
+/// sphero-cylinder of surface powers, e.g. `−8.00 −1.00 × 90` for a toric back
+/// curve of −8.00 D at 90° and −9.00 D at 180°. Errors are relative to `rx`,
+/// so they include any on-axis error of the lens.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // −4.00 D ordered, but surfaced with a −9.00 back curve on a 4 base
+/// let rx = SpheroCyl { sphere: -4.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let form = LensForm { front_curve: 4.0, center_thickness_mm: 2.0, index: 1.5 };
+/// let back = SpheroCyl { sphere: -9.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let p = oblique_power_with_back_surface(rx, form, back, DEFAULT_CENTER_OF_ROTATION_MM, 0.0, 0.0);
+/// // On axis: 4 / (1 − 0.002 / 1.5 × 4) − 9 = −4.979 D
+/// assert_abs_diff_eq!(p.mean_oblique_error, -0.979, epsilon = 1e-3);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `center_of_rotation_mm` is not positive.
+pub fn oblique_power_with_back_surface(
+    rx: SpheroCyl,
+    form: LensForm,
+    back_surface: SpheroCyl,
+    center_of_rotation_mm: f64,
+    gaze_meridian_deg: f64,
+    gaze_deg: f64,
+) -> ObliquePower {
+    debug_assert!(
+        center_of_rotation_mm > 0.0,
+        "center_of_rotation_mm must be positive"
+    );
+
+    let target_tangential = rx.power_at(gaze_meridian_deg);
+    let target_sagittal = rx.power_at(gaze_meridian_deg + 90.0);
+    let (tangential, sagittal) = trace(
+        form,
+        back_surface.power_at(gaze_meridian_deg),
+        back_surface.power_at(gaze_meridian_deg + 90.0),
+        center_of_rotation_mm,
+        gaze_deg,
+    );
+
+    let tangential_error = tangential - target_tangential;
+    let sagittal_error = sagittal - target_sagittal;
+    ObliquePower {
+        gaze_deg,
+        tangential,
+        sagittal,
+        oblique_astigmatism: tangential_error - sagittal_error,
+        mean_oblique_error: (tangential_error + sagittal_error) / 2.0,
+    }
+}
+
+/// Compute [`oblique_power`] across a range of gaze angles.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// let rx = SpheroCyl { sphere: 3.0, cylinder: -1.0, axis_deg: 90.0 };
+/// let form = LensForm { front_curve: 8.0, center_thickness_mm: 4.5, index: 1.6 };
+/// let gazes = [0.0, 10.0, 20.0, 30.0];
+/// let profile = oblique_power_profile(rx, form, DEFAULT_CENTER_OF_ROTATION_MM, 0.0, &gazes);
+/// assert_eq!(profile.len(), 4);
+/// assert!(profile[0].mean_oblique_error.abs() < 1e-9);
+/// ```
+pub fn oblique_power_profile(
+    rx: SpheroCyl,
+    form: LensForm,
+    center_of_rotation_mm: f64,
+    gaze_meridian_deg: f64,
+    gaze_angles_deg: &[f64],
+) -> Vec<ObliquePower> {
+    gaze_angles_deg
+        .iter()
+        .map(|&gaze| oblique_power(rx, form, center_of_rotation_mm, gaze_meridian_deg, gaze))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn on_axis_matches_back_vertex_power() {
+        let rx = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -1.5,
+            axis_deg: 30.0,
+        };
+        let form = LensForm {
+            front_curve: 4.0,
+            center_thickness_mm: 2.0,
+            index: 1.67,
+        };
+        for meridian in [0.0, 45.0, 120.0] {
+            let p = oblique_power(rx, form, 27.0, meridian, 0.0);
+            assert_abs_diff_eq!(p.tangential, rx.power_at(meridian), epsilon = EPS);
+            assert_abs_diff_eq!(p.sagittal, rx.power_at(meridian + 90.0), epsilon = EPS);
+        }
+    }
+
+    #[test]
+    fn given_back_curve_matches_coddington_by_hand() {
+        // 6 base, −10.00 back curve, 2 mm, n = 1.5, 30° gaze, 27 mm to the CoR.
+        // Chief ray angles (air/glass): front 22.396°/14.715°, back 13.297°/8.820°;
+        // 2.916 mm inside the lens and 1.741 mm on to the vertex sphere.
+        // Front Φ = 6.315 D → S 6.393, T 6.840 D at the back surface, where
+        // Φ = −10.181 D. The BVP on axis is 6 / (1 − 0.002 / 1.5 × 6) − 10 = −3.952 D.
+        let rx = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let form = LensForm {
+            front_curve: 6.0,
+            center_thickness_mm: 2.0,
+            index: 1.5,
+        };
+        let back = SpheroCyl {
+            sphere: -10.0,
+            ..rx
+        };
+        let p = oblique_power_with_back_surface(rx, form, back, 27.0, 0.0, 30.0);
+        assert_abs_diff_eq!(p.tangential, -3.674110, epsilon = 1e-5);
+        assert_abs_diff_eq!(p.sagittal, -3.763325, epsilon = 1e-5);
+        assert_abs_diff_eq!(p.oblique_astigmatism, 0.089214, epsilon = 1e-5);
+        assert_abs_diff_eq!(
+            p.mean_oblique_error,
+            (-3.674110 - 3.763325) / 2.0 + 4.0,
+            epsilon = 1e-5
+        );
+
+        let on_axis = oblique_power_with_back_surface(rx, form, back, 27.0, 0.0, 0.0);
+        assert_abs_diff_eq!(on_axis.tangential, -3.951613, epsilon = 1e-6);
+    }
+
+    #[test]
+    fn symmetric_in_gaze_direction() {
+        let form = LensForm {
+            front_curve: 2.0,
+            center_thickness_mm: 2.2,
+            index: 1.5,
+        };
+        let rx = SpheroCyl {
+            sphere: -5.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let up = oblique_power(rx, form, 27.0, 90.0, 25.0);
+        let down = oblique_power(rx, form, 27.0, 90.0, -25.0);
+        assert_abs_diff_eq!(up.tangential, down.tangential, epsilon = EPS);
+        assert_abs_diff_eq!(up.sagittal, down.sagittal, epsilon = EPS);
+    }
+
+    #[test]
+    fn oblique_astigmatism_changes_sign_through_tscherning_form() {
+        // For −4.00 D the point-focal form lies between flat and steep bases
+        let rx = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let at = |front_curve| {
+            let form = LensForm {
+                front_curve,
+                center_thickness_mm: 2.0,
+                index: 1.5,
+            };
+            oblique_power(rx, form, 27.0, 0.0, 30.0).oblique_astigmatism
+        };
+        let (flat, steep) = (at(0.0), at(10.0));
+        assert!(
+            flat.signum() != steep.signum(),
+            "flat {flat}, steep {steep}"
+        );
+        assert!(at(0.0).abs() > 0.2);
+    }
+
+    #[test]
+    fn plano_lens_has_almost_no_error() {
+        let form = LensForm {
+            front_curve: 6.0,
+            center_thickness_mm: 2.0,
+            index: 1.5,
+        };
+        let plano = SpheroCyl {
+            sphere: 0.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let p = oblique_power(plano, form, 27.0, 0.0, 30.0);
+        assert!(p.oblique_astigmatism.abs() < 0.1);
+        assert!(p.mean_oblique_error.abs() < 0.1);
+    }
+
+    #[test]
+    fn error_grows_with_gaze_for_flat_lens() {
+        let form = LensForm {
+            front_curve: 0.0,
+            center_thickness_mm: 6.0,
+            index: 1.5,
+        };
+        let rx = SpheroCyl {
+            sphere: 6.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let profile = oblique_power_profile(rx, form, 27.0, 0.0, &[0.0, 10.0, 20.0, 30.0]);
+        for pair in profile.windows(2) {
+            assert!(pair[1].oblique_astigmatism.abs() > pair[0].oblique_astigmatism.abs());
+        }
+    }
+}
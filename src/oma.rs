@@ -0,0 +1,757 @@
+//! OMA / VCA Data Communication Standard (DCS) records.
+//!
+//! ## Overview
+//! Tracers, edgers and lab software exchange jobs as lines of `LABEL=value`
+//! records. Per-eye records carry a right and a left value separated by a
+//! semicolon, e.g. `SPH=-2.00;-2.25`. [`OmaJob`] parses these into the crate's
+//! types and writes them back out with [`Display`](std::fmt::Display).
+//!
+//! Enabled with the `oma` cargo feature.
+//!
+//! ## Supported Records
+//! ```text
+//! JOB                    Job identifier
+//! SPH, CYL, AX, ADD      Lens power per eye (D, degrees)
+//! PRVM, PRVA             Prism magnitude (Δ) and base angle (degrees) per eye
+//! IPD, OCHT              Monocular PD and optical center height per eye (mm)
+//! HBOX, VBOX, FED, FEDAX Boxing A, B, effective diameter and its angle
+//! DBL                    Distance between lenses (mm)
+//! TRCFMT, R, A           ASCII (format 1) trace: radii and angles in 1/100 mm and 1/100°
+//! ```
+//! Any other record is kept verbatim in [`OmaJob::other_records`] and written
+//! back unchanged.
+//!
+//! ## Conventions
+//! - A value of `?` means "not given".
+//! - A per-eye record with a single value applies it to both eyes.
+//! - An eye without a `SPH` value has no prescription and is written back as
+//!   `?`; any other lens record with a value for that eye is an error. A
+//!   missing `CYL` means no cylinder, so `AX` may then be missing too.
+//! - Angles (`PRVA`, `FEDAX`, trace angles) run counter-clockwise from the
+//!   observer's right as viewed from the front of the frame, so 0° is nasal for
+//!   the right lens and temporal for the left lens.
+//! - Frame measurements are taken from the right-eye values and written for
+//!   both eyes. If `FED` is missing, the box diagonal is used as a
+//!   conservative effective diameter.
+//! - Output uses CR/LF line endings and two decimals for all numeric values.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::*;
+
+/// Number of trace values written per `R=` or `A=` record.
+const TRACE_VALUES_PER_RECORD: usize = 10;
+
+/// A record that is not interpreted by [`OmaJob`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OmaRecord {
+    /// Record label, e.g. `"LNAM"`.
+    pub label: String,
+
+    /// Raw record value, without the `=`.
+    pub value: String,
+}
+
+/// A frame trace from `TRCFMT` and its `R=`/`A=` records.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OmaTrace {
+    /// Eye the trace belongs to.
+    pub eye: Eye,
+
+    /// Radii from the boxing center (mm).
+    pub radii_mm: Vec<f64>,
+
+    /// Angle of each radius in degrees, or `None` if the radii are equiangular
+    /// starting at 0°.
+    pub angles_deg: Option<Vec<f64>>,
+}
+
+impl TryFrom<OmaTrace> for FrameTrace {
+    type Error = OmaError;
+
+    /// Converts the trace, failing if it has fewer than three points or its
+    /// radii and angles differ in count. The error is not tied to a record, so
+    /// its line is 0.
+    fn try_from(trace: OmaTrace) -> Result<Self, Self::Error> {
+        match &trace.angles_deg {
+            Some(angles) => FrameTrace::from_polar(trace.eye, &trace.radii_mm, angles),
+            None => FrameTrace::from_radii(trace.eye, &trace.radii_mm),
+        }
+        .ok_or_else(|| OmaError::new(0, "a trace needs at least three points with one angle each"))
+    }
+}
+
+/// A DCS job parsed from OMA records.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct OmaJob {
+    /// Job identifier from `JOB=`.
+    pub job: Option<String>,
+
+    /// Right-eye prescription, if `SPH` has a right value.
+    pub od: Option<EyeRx>,
+
+    /// Left-eye prescription, if `SPH` has a left value.
+    pub os: Option<EyeRx>,
+
+    /// Frame measurements, if `HBOX`, `VBOX` and `DBL` are present.
+    pub frame: Option<FrameMeasurements>,
+
+    /// Traces in the order they appear.
+    pub traces: Vec<OmaTrace>,
+
+    /// Records not interpreted above, in the order they appear.
+    pub other_records: Vec<OmaRecord>,
+}
+
+impl OmaJob {
+    /// Returns the prescription for one eye, if the job has one.
+    pub fn eye(&self, eye: Eye) -> Option<&EyeRx> {
+        match eye {
+            Eye::OD => self.od.as_ref(),
+            Eye::OS => self.os.as_ref(),
+        }
+    }
+
+    /// Returns the spectacle prescription, if the job has both eyes.
+    pub fn rx(&self) -> Option<SpectacleRx> {
+        Some(SpectacleRx {
+            od: self.od?,
+            os: self.os?,
+        })
+    }
+}
+
+/// Error returned when OMA records cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OmaError {
+    line: usize,
+    message: String,
+}
+
+impl OmaError {
+    fn new(line: usize, message: impl Into<String>) -> Self {
+        Self {
+            line,
+            message: message.into(),
+        }
+    }
+
+    /// Returns the 1-based line number of the offending record, or 0 if the
+    /// error is not tied to a record.
+    pub fn line(&self) -> usize {
+        self.line
+    }
+}
+
+impl fmt::Display for OmaError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "OMA line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for OmaError {}
+
+/// Right and left values of a per-eye record.
+type PerEye = [Option<f64>; 2];
+
+/// Parses a numeric value, treating `?` and empty values as missing.
+fn number(line: usize, label: &str, s: &str) -> Result<Option<f64>, OmaError> {
+    let s = s.trim();
+    if s.is_empty() || s == "?" {
+        return Ok(None);
+    }
+    s.parse::<f64>()
+        .map(Some)
+        .map_err(|_| OmaError::new(line, format!("invalid number {s:?} in {label}")))
+}
+
+/// Parses a per-eye `R;L` value.
+fn per_eye(line: usize, label: &str, value: &str) -> Result<PerEye, OmaError> {
+    let values: Vec<&str> = value.split(';').collect();
+    match values.as_slice() {
+        [both] => {
+            let v = number(line, label, both)?;
+            Ok([v, v])
+        }
+        [right, left] => Ok([number(line, label, right)?, number(line, label, left)?]),
+        _ => Err(OmaError::new(
+            line,
+            format!("expected one or two values in {label}"),
+        )),
+    }
+}
+
+/// Converts a DCS prism magnitude and base angle into a [`CombinedPrism`].
+fn prism_from_polar(eye: Eye, magnitude: f64, angle_deg: f64) -> CombinedPrism {
+    let (sin, cos) = angle_deg.to_radians().sin_cos();
+    // 0° points nasally (base in) for OD and temporally (base out) for OS
+    let base_out = match eye {
+        Eye::OD => -magnitude * cos,
+        Eye::OS => magnitude * cos,
+    };
+    CombinedPrism {
+        horizontal: HorizontalPrism::from_signed(base_out),
+        vertical: VerticalPrism::from_signed(magnitude * sin),
+    }
+}
+
+/// Converts a [`CombinedPrism`] into a DCS prism magnitude and base angle.
+fn prism_to_polar(eye: Eye, prism: CombinedPrism) -> (f64, f64) {
+    let x = match eye {
+        Eye::OD => -prism.horizontal.signed(),
+        Eye::OS => prism.horizontal.signed(),
+    };
+    let angle_deg = prism
+        .vertical
+        .signed()
+        .atan2(x)
+        .to_degrees()
+        .rem_euclid(360.0);
+    (prism.magnitude(), angle_deg)
+}
+
+/// A trace whose `R=`/`A=` records are still being read.
+struct PendingTrace {
+    line: usize,
+    count: usize,
+    trace: OmaTrace,
+}
+
+impl PendingTrace {
+    /// Parses a `TRCFMT=format;count;E|U;R|L;type` value.
+    fn start(line: usize, value: &str) -> Result<Self, OmaError> {
+        let fields: Vec<&str> = value.split(';').map(str::trim).collect();
+        if fields.len() < 4 {
+            return Err(OmaError::new(
+                line,
+                "TRCFMT needs format, count, mode and side",
+            ));
+        }
+        if fields[0] != "1" {
+            return Err(OmaError::new(
+                line,
+                format!("unsupported trace format {:?}", fields[0]),
+            ));
+        }
+        let count = fields[1]
+            .parse::<usize>()
+            .map_err(|_| OmaError::new(line, format!("invalid trace count {:?}", fields[1])))?;
+        if count < 3 {
+            return Err(OmaError::new(line, "a trace needs at least three points"));
+        }
+        let angles_deg = match fields[2] {
+            "E" => None,
+            "U" => Some(Vec::with_capacity(count)),
+            other => {
+                return Err(OmaError::new(line, format!("unknown trace mode {other:?}")));
+            }
+        };
+        let eye = match fields[3] {
+            "R" => Eye::OD,
+            "L" => Eye::OS,
+            other => return Err(OmaError::new(line, format!("unknown trace side {other:?}"))),
+        };
+        Ok(Self {
+            line,
+            count,
+            trace: OmaTrace {
+                eye,
+                radii_mm: Vec::with_capacity(count),
+                angles_deg,
+            },
+        })
+    }
+
+    /// Checks that the expected number of values was read.
+    fn finish(self) -> Result<OmaTrace, OmaError> {
+        let radii = self.trace.radii_mm.len();
+        let angles = self.trace.angles_deg.as_ref().map_or(self.count, Vec::len);
+        if radii != self.count || angles != self.count {
+            return Err(OmaError::new(
+                self.line,
+                format!(
+                    "trace declares {} points but has {radii} radii and {angles} angles",
+                    self.count
+                ),
+            ));
+        }
+        Ok(self.trace)
+    }
+}
+
+/// Parses `;`-separated trace values in hundredths.
+fn hundredths(line: usize, label: &str, value: &str) -> Result<Vec<f64>, OmaError> {
+    value
+        .split(';')
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| {
+            s.parse::<i64>()
+                .map(|v| v as f64 / 100.0)
+                .map_err(|_| OmaError::new(line, format!("invalid trace value {s:?} in {label}")))
+        })
+        .collect()
+}
+
+impl FromStr for OmaJob {
+    type Err = OmaError;
+
+    /// Parses OMA records, one per line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    ///
+    /// let job: OmaJob = "JOB=1042\r\nSPH=-2.00;-2.25\r\nCYL=-0.50;?\r\nAX=180;90\r\nIPD=32.0;31.5\r\n"
+    ///     .parse()
+    ///     .unwrap();
+    /// let rx = job.rx().unwrap();
+    /// assert_eq!(rx.od.lens.cylinder, -0.50);
+    /// assert_eq!(rx.os.lens.cylinder, 0.0);
+    /// assert_eq!(rx.binocular_pd_mm(), Some(63.5));
+    ///
+    /// // Single-lens job: the left eye stays unknown
+    /// let job: OmaJob = "SPH=-2.00;?\r\n".parse().unwrap();
+    /// assert!(job.os.is_none());
+    /// assert_eq!(job.to_string(), "SPH=-2.00;?\r\n");
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut job = OmaJob::default();
+        let mut per_eye_records: Vec<(&str, PerEye, usize)> = Vec::new();
+        let mut dbl_mm = None;
+        let mut pending: Option<PendingTrace> = None;
+
+        for (index, raw) in s.lines().enumerate() {
+            let line = index + 1;
+            let raw = raw.trim();
+            if raw.is_empty() {
+                continue;
+            }
+            let (label, value) = raw
+                .split_once('=')
+                .ok_or_else(|| OmaError::new(line, "expected LABEL=value"))?;
+            let label = label.trim();
+
+            if label != "R"
+                && label != "A"
+                && let Some(trace) = pending.take()
+            {
+                job.traces.push(trace.finish()?);
+            }
+
+            match label {
+                "TRCFMT" => pending = Some(PendingTrace::start(line, value)?),
+                "R" | "A" => {
+                    let trace = pending
+                        .as_mut()
+                        .ok_or_else(|| OmaError::new(line, format!("{label}= without TRCFMT")))?;
+                    let values = hundredths(line, label, value)?;
+                    match (label, trace.trace.angles_deg.as_mut()) {
+                        ("R", _) => trace.trace.radii_mm.extend(values),
+                        (_, Some(angles)) => angles.extend(values),
+                        (_, None) => {
+                            return Err(OmaError::new(line, "A= in an equiangular trace"));
+                        }
+                    }
+                }
+                "JOB" => job.job = Some(value.trim().to_string()),
+                "DBL" => dbl_mm = per_eye(line, label, value)?[0],
+                "SPH" | "CYL" | "AX" | "ADD" | "PRVM" | "PRVA" | "IPD" | "OCHT" | "HBOX"
+                | "VBOX" | "FED" | "FEDAX" => {
+                    per_eye_records.push((label, per_eye(line, label, value)?, line));
+                }
+                _ => job.other_records.push(OmaRecord {
+                    label: label.to_string(),
+                    value: value.to_string(),
+                }),
+            }
+        }
+        if let Some(trace) = pending.take() {
+            job.traces.push(trace.finish()?);
+        }
+
+        let record = |label: &str| {
+            per_eye_records
+                .iter()
+                .rev()
+                .find(|(l, _, _)| *l == label)
+                .map(|(_, v, line)| (*v, *line))
+        };
+        let get = |label: &str| record(label).map_or([None, None], |(v, _)| v);
+        let line_of = |label: &str| record(label).map_or(0, |(_, line)| line);
+
+        let sph = get("SPH");
+        let (cyl, ax, add) = (get("CYL"), get("AX"), get("ADD"));
+        let (prvm, prva, ipd, ocht) = (get("PRVM"), get("PRVA"), get("IPD"), get("OCHT"));
+        for (i, eye) in [(0, "right"), (1, "left")] {
+            if sph[i].is_some() {
+                continue;
+            }
+            for label in ["CYL", "AX", "ADD", "PRVM", "PRVA", "IPD", "OCHT"] {
+                if get(label)[i].is_some() {
+                    return Err(OmaError::new(
+                        line_of(label),
+                        format!("{label} for the {eye} eye without SPH"),
+                    ));
+                }
+            }
+        }
+        let eye_rx = |i: usize, eye: Eye| -> Result<Option<EyeRx>, OmaError> {
+            let Some(sphere) = sph[i] else {
+                return Ok(None);
+            };
+            let cylinder = cyl[i].unwrap_or(0.0);
+            let axis_deg = match ax[i] {
+                Some(axis_deg) => axis_deg,
+                None if cylinder == 0.0 => 0.0,
+                None => return Err(OmaError::new(line_of("CYL"), "CYL without AX")),
+            };
+            let mut rx = EyeRx::from_lens(SpheroCyl {
+                sphere,
+                cylinder,
+                axis_deg,
+            });
+            rx.add = add[i];
+            match (prvm[i], prva[i]) {
+                (Some(magnitude), Some(angle_deg)) => {
+                    rx.prism = prism_from_polar(eye, magnitude, angle_deg);
+                }
+                (Some(magnitude), None) if magnitude != 0.0 => {
+                    return Err(OmaError::new(line_of("PRVM"), "PRVM without PRVA"));
+                }
+                _ => {}
+            }
+            rx.monocular_pd_mm = ipd[i];
+            rx.oc_height_mm = ocht[i];
+            Ok(Some(rx))
+        };
+        job.od = eye_rx(0, Eye::OD)?;
+        job.os = eye_rx(1, Eye::OS)?;
+
+        let first = |v: PerEye| v[0].or(v[1]);
+        if let (Some(a_mm), Some(b_mm), Some(dbl_mm)) =
+            (first(get("HBOX")), first(get("VBOX")), dbl_mm)
+        {
+            job.frame = Some(FrameMeasurements {
+                a_mm,
+                b_mm,
+                dbl_mm,
+                ed_mm: first(get("FED")).unwrap_or_else(|| a_mm.hypot(b_mm)),
+                ed_axis_deg: first(get("FEDAX")),
+            });
+        }
+
+        Ok(job)
+    }
+}
+
+/// Writes a per-eye record if either value is present.
+fn write_per_eye(f: &mut fmt::Formatter<'_>, label: &str, values: PerEye) -> fmt::Result {
+    if values.iter().all(Option::is_none) {
+        return Ok(());
+    }
+    let value = |v: Option<f64>| v.map_or_else(|| "?".to_string(), |v| format!("{v:.2}"));
+    write!(f, "{label}={};{}\r\n", value(values[0]), value(values[1]))
+}
+
+/// Writes trace values in hundredths, several per record.
+fn write_hundredths(f: &mut fmt::Formatter<'_>, label: &str, values: &[f64]) -> fmt::Result {
+    for chunk in values.chunks(TRACE_VALUES_PER_RECORD) {
+        let chunk: Vec<String> = chunk
+            .iter()
+            .map(|v| format!("{}", (v * 100.0).round() as i64))
+            .collect();
+        write!(f, "{label}={}\r\n", chunk.join(";"))?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for OmaJob {
+    /// Writes the job as OMA records with CR/LF line endings.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if let Some(job) = &self.job {
+            write!(f, "JOB={job}\r\n")?;
+        }
+
+        let eyes = [self.od.as_ref(), self.os.as_ref()];
+        let both = |value: fn(&EyeRx) -> Option<f64>| eyes.map(|e| e.and_then(value));
+        let any = |test: fn(&EyeRx) -> bool| eyes.iter().flatten().any(|e| test(e));
+        write_per_eye(f, "SPH", both(|e| Some(e.lens.sphere)))?;
+        // Spheres need no CYL or AX records
+        if any(|e| e.lens.cylinder != 0.0) {
+            write_per_eye(f, "CYL", both(|e| Some(e.lens.cylinder)))?;
+            write_per_eye(f, "AX", both(|e| Some(e.lens.axis_deg)))?;
+        }
+        write_per_eye(f, "ADD", both(|e| e.add))?;
+        if any(|e| e.prism.magnitude() > 0.0) {
+            let polar = |eye: Eye| self.eye(eye).map(|e| prism_to_polar(eye, e.prism));
+            let (od, os) = (polar(Eye::OD), polar(Eye::OS));
+            write_per_eye(f, "PRVM", [od.map(|p| p.0), os.map(|p| p.0)])?;
+            write_per_eye(f, "PRVA", [od.map(|p| p.1), os.map(|p| p.1)])?;
+        }
+        write_per_eye(f, "IPD", both(|e| e.monocular_pd_mm))?;
+        write_per_eye(f, "OCHT", both(|e| e.oc_height_mm))?;
+
+        if let Some(frame) = &self.frame {
+            write_per_eye(f, "HBOX", [Some(frame.a_mm); 2])?;
+            write_per_eye(f, "VBOX", [Some(frame.b_mm); 2])?;
+            write!(f, "DBL={:.2}\r\n", frame.dbl_mm)?;
+            write_per_eye(f, "FED", [Some(frame.ed_mm); 2])?;
+            write_per_eye(f, "FEDAX", [frame.ed_axis_deg; 2])?;
+        }
+
+        for record in &self.other_records {
+            write!(f, "{}={}\r\n", record.label, record.value)?;
+        }
+
+        for trace in &self.traces {
+            let mode = if trace.angles_deg.is_some() { "U" } else { "E" };
+            let side = match trace.eye {
+                Eye::OD => "R",
+                Eye::OS => "L",
+            };
+            write!(f, "TRCFMT=1;{};{mode};{side};F\r\n", trace.radii_mm.len())?;
+            write_hundredths(f, "R", &trace.radii_mm)?;
+            if let Some(angles) = &trace.angles_deg {
+                write_hundredths(f, "A", angles)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    const PROGRESSIVE_JOB: &str = include_str!("../testdata/oma/progressive.oma");
+    const UNEQUAL_TRACE_JOB: &str = include_str!("../testdata/oma/unequal_trace.oma");
+
+    #[test]
+    fn parses_progressive_job() {
+        let job: OmaJob = PROGRESSIVE_JOB.parse().unwrap();
+        assert_eq!(job.job.as_deref(), Some("24117"));
+
+        let rx = job.rx().unwrap();
+        assert_abs_diff_eq!(rx.od.lens.sphere, -2.25, epsilon = EPS);
+        assert_abs_diff_eq!(rx.os.lens.cylinder, -0.75, epsilon = EPS);
+        assert_abs_diff_eq!(rx.os.lens.axis_deg, 170.0, epsilon = EPS);
+        assert_eq!(rx.od.add, Some(2.0));
+        assert_eq!(rx.binocular_pd_mm(), Some(63.0));
+        assert_eq!(rx.os.oc_height_mm, Some(21.5));
+
+        // PRVA 270 on OD is base down; PRVA 0 on OS is base out
+        assert_abs_diff_eq!(rx.od.prism.vertical.signed(), -1.0, epsilon = EPS);
+        assert_abs_diff_eq!(rx.od.prism.horizontal.amount(), 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(rx.os.prism.horizontal.signed(), 0.5, epsilon = EPS);
+
+        let frame = job.frame.unwrap();
+        assert_eq!(frame.frame_pd_mm(), 69.0);
+        assert_eq!(frame.ed_mm, 54.5);
+
+        assert_eq!(job.traces.len(), 2);
+        assert_eq!(job.traces[0].eye, Eye::OD);
+        assert_eq!(job.traces[1].eye, Eye::OS);
+        assert_eq!(job.traces[0].radii_mm.len(), 36);
+        let shape = FrameTrace::try_from(job.traces[0].clone()).unwrap();
+        assert_abs_diff_eq!(shape.a_mm(), 51.0, epsilon = EPS);
+        assert_abs_diff_eq!(shape.b_mm(), 38.0, epsilon = EPS);
+        assert_abs_diff_eq!(job.traces[0].radii_mm[0], 25.5, epsilon = EPS);
+        assert!(job.traces[0].angles_deg.is_none());
+
+        let labels: Vec<&str> = job.other_records.iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, ["LNAM", "LMATTYPE"]);
+    }
+
+    #[test]
+    fn parses_unequal_trace_and_missing_values() {
+        let job: OmaJob = UNEQUAL_TRACE_JOB.parse().unwrap();
+        let rx = job.rx().unwrap();
+        // Single-value records apply to both eyes
+        assert_abs_diff_eq!(rx.os.lens.sphere, 1.5, epsilon = EPS);
+        assert_eq!(rx.od.monocular_pd_mm, None);
+        assert_eq!(rx.os.monocular_pd_mm, Some(30.5));
+        // FED missing → box diagonal
+        let frame = job.frame.unwrap();
+        assert_abs_diff_eq!(frame.ed_mm, 48.0f64.hypot(34.0), epsilon = EPS);
+
+        let trace = &job.traces[0];
+        let angles = trace.angles_deg.as_ref().unwrap();
+        assert_eq!(angles.len(), trace.radii_mm.len());
+        assert_abs_diff_eq!(angles[1], 12.5, epsilon = EPS);
+    }
+
+    /// Asserts that two jobs hold the same values.
+    fn assert_same_job(a: &OmaJob, b: &OmaJob) {
+        assert_eq!(a.job, b.job);
+        for eye in [Eye::OD, Eye::OS] {
+            assert_eq!(a.eye(eye).is_some(), b.eye(eye).is_some());
+            let (Some(a), Some(b)) = (a.eye(eye), b.eye(eye)) else {
+                continue;
+            };
+            assert_abs_diff_eq!(a.lens.sphere, b.lens.sphere, epsilon = EPS);
+            assert_abs_diff_eq!(a.lens.cylinder, b.lens.cylinder, epsilon = EPS);
+            assert_abs_diff_eq!(a.lens.axis_deg, b.lens.axis_deg, epsilon = EPS);
+            assert_eq!(a.add, b.add);
+            let (ah, bh) = (a.prism.horizontal.signed(), b.prism.horizontal.signed());
+            let (av, bv) = (a.prism.vertical.signed(), b.prism.vertical.signed());
+            assert_abs_diff_eq!(ah, bh, epsilon = EPS);
+            assert_abs_diff_eq!(av, bv, epsilon = EPS);
+            assert_eq!(a.monocular_pd_mm, b.monocular_pd_mm);
+            assert_eq!(a.oc_height_mm, b.oc_height_mm);
+        }
+        assert_eq!(a.frame.is_some(), b.frame.is_some());
+        if let (Some(a), Some(b)) = (a.frame, b.frame) {
+            assert_eq!(a.a_mm, b.a_mm);
+            assert_eq!(a.b_mm, b.b_mm);
+            assert_eq!(a.dbl_mm, b.dbl_mm);
+            assert_abs_diff_eq!(a.ed_mm, b.ed_mm, epsilon = 0.005);
+            assert_eq!(a.ed_axis_deg, b.ed_axis_deg);
+        }
+        assert_eq!(a.traces.len(), b.traces.len());
+        for (a, b) in a.traces.iter().zip(&b.traces) {
+            assert_eq!(a.eye, b.eye);
+            assert_eq!(a.radii_mm, b.radii_mm);
+            assert_eq!(a.angles_deg, b.angles_deg);
+        }
+        assert_eq!(a.other_records, b.other_records);
+    }
+
+    #[test]
+    fn sample_jobs_match_their_records() {
+        let job: OmaJob = PROGRESSIVE_JOB.parse().unwrap();
+        let (od, os) = (job.od.unwrap(), job.os.unwrap());
+        assert_eq!(
+            (od.lens.sphere, od.lens.cylinder, od.lens.axis_deg),
+            (-2.25, -0.5, 5.0)
+        );
+        assert_eq!(
+            (os.lens.sphere, os.lens.cylinder, os.lens.axis_deg),
+            (-2.0, -0.75, 170.0)
+        );
+        assert_eq!(
+            (od.monocular_pd_mm, os.monocular_pd_mm),
+            (Some(32.0), Some(31.0))
+        );
+        assert_eq!((od.oc_height_mm, os.oc_height_mm), (Some(22.0), Some(21.5)));
+        let frame = job.frame.unwrap();
+        assert_eq!((frame.a_mm, frame.b_mm, frame.dbl_mm), (51.0, 38.0, 18.0));
+        assert_eq!(job.traces.len(), 2);
+
+        let job: OmaJob = UNEQUAL_TRACE_JOB.parse().unwrap();
+        let (od, os) = (job.od.unwrap(), job.os.unwrap());
+        assert_eq!(
+            (od.lens.sphere, od.lens.cylinder, od.lens.axis_deg),
+            (1.5, -1.0, 90.0)
+        );
+        assert_eq!(
+            (os.lens.sphere, os.lens.cylinder, os.lens.axis_deg),
+            (1.5, -1.25, 85.0)
+        );
+        assert_eq!((od.monocular_pd_mm, os.monocular_pd_mm), (None, Some(30.5)));
+        assert_eq!((od.oc_height_mm, os.oc_height_mm), (None, None));
+        let frame = job.frame.unwrap();
+        assert_eq!((frame.a_mm, frame.b_mm, frame.dbl_mm), (48.0, 34.0, 20.0));
+        assert_eq!(job.traces.len(), 1);
+        assert_eq!(job.traces[0].radii_mm.len(), 48);
+    }
+
+    #[test]
+    fn sample_jobs_round_trip() {
+        for sample in [PROGRESSIVE_JOB, UNEQUAL_TRACE_JOB] {
+            let first: OmaJob = sample.parse().unwrap();
+            let written = first.to_string();
+            let second: OmaJob = written.parse().unwrap();
+            assert_same_job(&first, &second);
+            assert_eq!(second.to_string(), written);
+        }
+    }
+
+    #[test]
+    fn missing_eye_stays_missing() {
+        let job: OmaJob = "SPH=-2.00;?\r\n".parse().unwrap();
+        assert_eq!(job.od.unwrap().lens.sphere, -2.0);
+        assert!(job.os.is_none());
+        assert!(job.rx().is_none());
+        assert_eq!(job.to_string(), "SPH=-2.00;?\r\n");
+
+        let records = "SPH=?;1.25\r\nCYL=?;-0.50\r\nAX=?;15.00\r\nIPD=?;31.00\r\n";
+        let job: OmaJob = records.parse().unwrap();
+        assert!(job.od.is_none());
+        assert_eq!(job.to_string(), records);
+    }
+
+    #[test]
+    fn rejects_values_that_cannot_be_interpreted() {
+        // Cylinder for an eye without a sphere
+        let err = "SPH=-2.00;?\r\nCYL=-0.50;-0.75\r\nAX=90;90\r\n"
+            .parse::<OmaJob>()
+            .unwrap_err();
+        assert_eq!(err.line(), 2);
+        // Cylinder without an axis
+        let err = "SPH=-2.00;-2.00\r\nCYL=-0.50;-0.75\r\nAX=90;?\r\n"
+            .parse::<OmaJob>()
+            .unwrap_err();
+        assert_eq!(err.line(), 2);
+        // Prism without a base direction
+        let err = "SPH=0;0\r\nPRVM=1.00;?\r\n".parse::<OmaJob>().unwrap_err();
+        assert_eq!(err.line(), 2);
+    }
+
+    #[test]
+    fn prism_round_trips_through_polar_form() {
+        for eye in [Eye::OD, Eye::OS] {
+            let prism = CombinedPrism {
+                horizontal: HorizontalPrism::new(3.0, HorizontalBase::In),
+                vertical: VerticalPrism::new(4.0, VerticalBase::Up),
+            };
+            let (magnitude, angle_deg) = prism_to_polar(eye, prism);
+            assert_abs_diff_eq!(magnitude, 5.0, epsilon = EPS);
+            let back = prism_from_polar(eye, magnitude, angle_deg);
+            assert_abs_diff_eq!(back.horizontal.signed(), -3.0, epsilon = EPS);
+            assert_abs_diff_eq!(back.vertical.signed(), 4.0, epsilon = EPS);
+        }
+    }
+
+    #[test]
+    fn reports_line_of_bad_records() {
+        let err = "SPH=-1.00;-1.00\r\nCYL=abc;0\r\n"
+            .parse::<OmaJob>()
+            .unwrap_err();
+        assert_eq!(err.line(), 2);
+
+        let err = "TRCFMT=1;4;E;R;F\r\nR=2500;2500;2500\r\n"
+            .parse::<OmaJob>()
+            .unwrap_err();
+        assert_eq!(err.line(), 1);
+
+        let err = "R=2500\r\n".parse::<OmaJob>().unwrap_err();
+        assert_eq!(err.to_string(), "OMA line 1: R= without TRCFMT");
+
+        assert!("TRCFMT=2;4;E;R;F\r\n".parse::<OmaJob>().is_err());
+
+        let err = "TRCFMT=1;2;E;R;F\r\nR=2500;2500\r\n"
+            .parse::<OmaJob>()
+            .unwrap_err();
+        assert_eq!(err.line(), 1);
+    }
+
+    #[test]
+    fn short_trace_does_not_convert() {
+        let trace = OmaTrace {
+            eye: Eye::OD,
+            radii_mm: vec![25.0, 25.0],
+            angles_deg: None,
+        };
+        assert_eq!(FrameTrace::try_from(trace).unwrap_err().line(), 0);
+    }
+}
@@ -0,0 +1,359 @@
+//! Position-of-wear compensation for tilted and wrapped frames.
+//!
+//! ## Overview
+//! A lens tilted in front of the eye acts with more sphere power and an
+//! induced cylinder, and a lens fitted at a different vertex distance from the
+//! refraction has a different effective power. [`position_of_wear`] returns
+//! both the Rx the patient receives when the refraction is ordered unchanged
+//! and the compensated Rx to order instead.
+//!
+//! ## Formulas
+//! Martin's formulas for a thin lens of power F tilted by θ:
+//! ```text
+//! Sphere = F × (1 + sin²θ / 2n)
+//! Cyl    = Sphere × tan²θ, axis along the tilt axis
+//! ```
+//! Pantoscopic tilt rotates the lens about the horizontal axis (induced cyl
+//! axis 180); face-form wrap rotates it about the vertical axis (axis 90). For a
+//! sphero-cylinder, [`TiltModel::Martin`] scales the whole lens by the sphere
+//! factor and adds the induced cylinders computed from its spherical equivalent.
+//!
+//! [`TiltModel::Matrix`] instead stretches the dioptric power matrix along each
+//! tilted meridian:
+//! ```text
+//! F' = k × S F S,   S = diag(1 / cos wrap, 1 / cos panto),   k = 1 + sin²α / 2n
+//! cos α = cos panto × cos wrap
+//! ```
+//! which reduces to Martin's formulas for a sphere.
+//!
+//! Vertex distance changes are applied per principal meridian:
+//! ```text
+//! F' = F / (1 − d F),   d = distance moved toward the eye (m)
+//! ```
+//!
+//! ## Conventions
+//! - Angles are in degrees; the sign of the tilt does not matter.
+//! - Results are in minus-cylinder form with the axis in [0, 180).
+
+use crate::*;
+
+/// How tilt-induced power is computed for a sphero-cylinder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum TiltModel {
+    /// Martin's formulas applied to the spherical equivalent.
+    Martin,
+
+    /// Full dioptric power matrix treatment of the toric lens.
+    Matrix,
+}
+
+/// How a frame sits on the face compared with the refraction.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PositionOfWear {
+    /// Pantoscopic tilt (degrees).
+    /// Example: lower rims tilted 10° toward the cheeks → `pantoscopic_tilt_deg = 10.0`.
+    pub pantoscopic_tilt_deg: f64,
+
+    /// Face-form wrap (degrees).
+    pub face_form_wrap_deg: f64,
+
+    /// Back vertex distance of the fitted frame (mm).
+    pub vertex_distance_mm: f64,
+
+    /// Back vertex distance at which the refraction was measured (mm).
+    pub refraction_vertex_mm: f64,
+}
+
+/// Result of a position-of-wear calculation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PositionOfWearRx {
+    /// Rx the patient receives, at the refraction vertex, if the refraction is ordered unchanged.
+    pub as_worn: SpheroCyl,
+
+    /// Rx to order so the as-worn power matches the refraction.
+    pub compensated: SpheroCyl,
+}
+
+/// Transfer a sphero-cylinder from one vertex distance to another.
+///
+/// Returns the lens at `to_vertex_mm` that has the same effect as `rx` at
+/// `from_vertex_mm`. The cylinder form and axis are preserved.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // +10.00 D at 12 mm is +11.36 D at the cornea
+/// let rx = SpheroCyl { sphere: 10.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let cornea = vertex_compensate(rx, 12.0, 0.0);
+/// assert_abs_diff_eq!(cornea.sphere, 11.364, epsilon = 1e-3);
+/// ```
+pub fn vertex_compensate(rx: SpheroCyl, from_vertex_mm: f64, to_vertex_mm: f64) -> SpheroCyl {
+    let d = (from_vertex_mm - to_vertex_mm) / 1000.0;
+    let transfer = |power: f64| power / (1.0 - d * power);
+
+    let sphere = transfer(rx.sphere);
+    SpheroCyl {
+        sphere,
+        cylinder: transfer(rx.sphere + rx.cylinder) - sphere,
+        axis_deg: rx.axis_deg,
+    }
+}
+
+/// Martin's sphere factor `1 + sin²α / 2n` for the combined obliquity α.
+fn sphere_factor(pantoscopic_deg: f64, wrap_deg: f64, index: f64) -> f64 {
+    let cos_alpha = pantoscopic_deg.to_radians().cos() * wrap_deg.to_radians().cos();
+    1.0 + (1.0 - cos_alpha * cos_alpha) / (2.0 * index)
+}
+
+/// Compute the effective power of a thin lens tilted by pantoscopic tilt and wrap.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // +10.00 D tilted 10° pantoscopically in n = 1.5: +10.10 / +0.31 × 180
+/// let lens = SpheroCyl { sphere: 10.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let tilted = tilted_lens_power(lens, 10.0, 0.0, 1.5, TiltModel::Martin);
+/// assert_abs_diff_eq!(tilted.sphere + tilted.cylinder, 10.100, epsilon = 1e-3);
+/// assert_abs_diff_eq!(-tilted.cylinder, 0.314, epsilon = 1e-3);
+/// assert_abs_diff_eq!(tilted.axis_deg, 90.0, epsilon = 1e-9); // minus-cyl form
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if `index` is not greater than 1.
+pub fn tilted_lens_power(
+    lens: SpheroCyl,
+    pantoscopic_deg: f64,
+    wrap_deg: f64,
+    index: f64,
+    model: TiltModel,
+) -> SpheroCyl {
+    debug_assert!(index > 1.0, "index must be > 1.0");
+
+    let k = sphere_factor(pantoscopic_deg, wrap_deg, index);
+    let (cos_p, cos_w) = (
+        pantoscopic_deg.to_radians().cos(),
+        wrap_deg.to_radians().cos(),
+    );
+    match model {
+        TiltModel::Martin => {
+            let tan2_p = pantoscopic_deg.to_radians().tan().powi(2);
+            let tan2_w = wrap_deg.to_radians().tan().powi(2);
+            let sphere = k * (lens.sphere + lens.cylinder / 2.0);
+            // Induced cyl axis 180 acts in the vertical meridian, axis 90 in the horizontal
+            let [px, pt, py] = power_matrix(lens);
+            from_power_matrix([k * px + sphere * tan2_w, k * pt, k * py + sphere * tan2_p])
+        }
+        TiltModel::Matrix => {
+            let [px, pt, py] = power_matrix(lens);
+            from_power_matrix([
+                k * px / (cos_w * cos_w),
+                k * pt / (cos_w * cos_p),
+                k * py / (cos_p * cos_p),
+            ])
+        }
+    }
+}
+
+/// Compute the lens to order so that, once tilted, it acts as `target`.
+///
+/// Inverse of [`tilted_lens_power`].
+///
+/// # Panics
+/// Panics in debug builds if `index` is not greater than 1.
+pub fn untilted_lens_power(
+    target: SpheroCyl,
+    pantoscopic_deg: f64,
+    wrap_deg: f64,
+    index: f64,
+    model: TiltModel,
+) -> SpheroCyl {
+    debug_assert!(index > 1.0, "index must be > 1.0");
+
+    let k = sphere_factor(pantoscopic_deg, wrap_deg, index);
+    let (cos_p, cos_w) = (
+        pantoscopic_deg.to_radians().cos(),
+        wrap_deg.to_radians().cos(),
+    );
+    match model {
+        TiltModel::Martin => {
+            let tan2_p = pantoscopic_deg.to_radians().tan().powi(2);
+            let tan2_w = wrap_deg.to_radians().tan().powi(2);
+            // Spherical equivalent of the lens to order, before tilting
+            let lens_se =
+                (target.sphere + target.cylinder / 2.0) / (k * (1.0 + (tan2_p + tan2_w) / 2.0));
+            let [px, pt, py] = power_matrix(target);
+            from_power_matrix([px / k - lens_se * tan2_w, pt / k, py / k - lens_se * tan2_p])
+        }
+        TiltModel::Matrix => {
+            let [px, pt, py] = power_matrix(target);
+            from_power_matrix([
+                px * cos_w * cos_w / k,
+                pt * cos_w * cos_p / k,
+                py * cos_p * cos_p / k,
+            ])
+        }
+    }
+}
+
+/// Compute the as-worn and compensated Rx for a frame's position of wear.
+///
+/// `rx` is the refraction, measured at [`PositionOfWear::refraction_vertex_mm`],
+/// and `index` the refractive index of the lens material.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let rx = SpheroCyl { sphere: -4.0, cylinder: -1.0, axis_deg: 180.0 };
+/// let wear = PositionOfWear {
+///     pantoscopic_tilt_deg: 12.0,
+///     face_form_wrap_deg: 15.0,
+///     vertex_distance_mm: 14.0,
+///     refraction_vertex_mm: 12.0,
+/// };
+/// let result = position_of_wear(rx, wear, POLYCARBONATE_INDEX, TiltModel::Matrix);
+///
+/// // Wearing the compensated lens reproduces the refraction
+/// let check = position_of_wear(result.compensated, wear, POLYCARBONATE_INDEX, TiltModel::Matrix);
+/// assert_abs_diff_eq!(check.as_worn.sphere, rx.sphere, epsilon = 1e-9);
+/// assert_abs_diff_eq!(check.as_worn.cylinder, rx.cylinder, epsilon = 1e-9);
+/// ```
+pub fn position_of_wear(
+    rx: SpheroCyl,
+    wear: PositionOfWear,
+    index: f64,
+    model: TiltModel,
+) -> PositionOfWearRx {
+    let tilted = tilted_lens_power(
+        rx,
+        wear.pantoscopic_tilt_deg,
+        wear.face_form_wrap_deg,
+        index,
+        model,
+    );
+    let as_worn = vertex_compensate(tilted, wear.vertex_distance_mm, wear.refraction_vertex_mm);
+
+    let at_fitting = vertex_compensate(rx, wear.refraction_vertex_mm, wear.vertex_distance_mm);
+    let compensated = untilted_lens_power(
+        at_fitting,
+        wear.pantoscopic_tilt_deg,
+        wear.face_form_wrap_deg,
+        index,
+        model,
+    );
+
+    PositionOfWearRx {
+        as_worn,
+        compensated,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    fn assert_same_power(a: SpheroCyl, b: SpheroCyl) {
+        let (pa, pb) = (power_matrix(a), power_matrix(b));
+        for (x, y) in pa.iter().zip(pb) {
+            // Recovering a near-zero cylinder goes through a square root
+            assert_abs_diff_eq!(*x, y, epsilon = 1e-6);
+        }
+    }
+
+    #[test]
+    fn models_agree_for_spheres() {
+        let lens = SpheroCyl {
+            sphere: -6.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        for (panto, wrap) in [(10.0, 0.0), (0.0, 20.0), (8.0, 12.0)] {
+            let martin = tilted_lens_power(lens, panto, wrap, 1.6, TiltModel::Martin);
+            let matrix = tilted_lens_power(lens, panto, wrap, 1.6, TiltModel::Matrix);
+            assert_same_power(martin, matrix);
+        }
+    }
+
+    #[test]
+    fn wrap_induces_cylinder_at_axis_90() {
+        let lens = SpheroCyl {
+            sphere: -5.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let tilted = tilted_lens_power(lens, 0.0, 20.0, 1.5, TiltModel::Martin);
+        // Minus lens: induced minus cyl along the vertical tilt axis
+        let k = 1.0 + 20f64.to_radians().sin().powi(2) / 3.0;
+        assert_abs_diff_eq!(tilted.sphere, -5.0 * k, epsilon = EPS);
+        assert_abs_diff_eq!(
+            tilted.cylinder,
+            -5.0 * k * 20f64.to_radians().tan().powi(2),
+            epsilon = EPS
+        );
+        assert_abs_diff_eq!(tilted.axis_deg, 90.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn compensation_inverts_tilt_for_both_models() {
+        let target = SpheroCyl {
+            sphere: 2.5,
+            cylinder: -1.75,
+            axis_deg: 35.0,
+        };
+        for model in [TiltModel::Martin, TiltModel::Matrix] {
+            let lens = untilted_lens_power(target, 10.0, 18.0, 1.67, model);
+            assert_same_power(tilted_lens_power(lens, 10.0, 18.0, 1.67, model), target);
+        }
+    }
+
+    #[test]
+    fn vertex_transfer_round_trips_and_keeps_form() {
+        let rx = SpheroCyl {
+            sphere: -8.0,
+            cylinder: 2.0,
+            axis_deg: 80.0,
+        };
+        let cornea = vertex_compensate(rx, 13.0, 0.0);
+        assert!(cornea.cylinder > 0.0);
+        assert_abs_diff_eq!(cornea.sphere, -8.0 / 1.104, epsilon = EPS);
+        let back = vertex_compensate(cornea, 0.0, 13.0);
+        assert_abs_diff_eq!(back.sphere, rx.sphere, epsilon = EPS);
+        assert_abs_diff_eq!(back.cylinder, rx.cylinder, epsilon = EPS);
+    }
+
+    #[test]
+    fn neutral_position_of_wear_is_identity() {
+        let rx = SpheroCyl {
+            sphere: -3.0,
+            cylinder: -0.5,
+            axis_deg: 10.0,
+        };
+        let wear = PositionOfWear {
+            pantoscopic_tilt_deg: 0.0,
+            face_form_wrap_deg: 0.0,
+            vertex_distance_mm: 12.0,
+            refraction_vertex_mm: 12.0,
+        };
+        let result = position_of_wear(rx, wear, CR_39_INDEX, TiltModel::Martin);
+        assert_same_power(result.as_worn, rx);
+        assert_same_power(result.compensated, rx);
+    }
+}
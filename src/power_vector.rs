@@ -0,0 +1,175 @@
+//! Power vector representation of sphero-cylinders (Thibos).
+//!
+//! ## Overview
+//! A [`PowerVector`] writes a sphero-cylinder as three orthogonal components,
+//! so prescriptions can be added, subtracted, averaged and compared with
+//! ordinary vector arithmetic.
+//!
+//! ## Formulas
+//! ```text
+//! M   = S + C/2
+//! J0  = −(C/2)·cos(2α)
+//! J45 = −(C/2)·sin(2α)
+//! B   = √(M² + J0² + J45²)          (blur strength)
+//! ```
+//!
+//! ## Conventions
+//! - All components are in **diopters (D)**.
+//! - Conversion back to a sphero-cylinder gives minus-cylinder form with the
+//!   axis in [0, 180).
+
+use std::ops::{Add, Sub};
+
+use crate::*;
+
+/// Sphero-cylinder as a power vector.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PowerVector {
+    /// Spherical equivalent (D).
+    /// Example: −2.00 −1.00 × 180 → `m = -2.5`.
+    pub m: f64,
+
+    /// Cross-cylinder at 0°/90° (D).
+    /// Example: −2.00 −1.00 × 180 → `j0 = 0.5`.
+    pub j0: f64,
+
+    /// Cross-cylinder at 45°/135° (D).
+    /// Example: −2.00 −1.00 × 45 → `j45 = 0.5`.
+    pub j45: f64,
+}
+
+impl PowerVector {
+    /// Returns the blur strength, the length of the vector (D).
+    pub fn magnitude(&self) -> f64 {
+        (self.m * self.m + self.j0 * self.j0 + self.j45 * self.j45).sqrt()
+    }
+
+    /// Returns the distance between two power vectors (D).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let a = PowerVector::from(SpheroCyl { sphere: -2.0, cylinder: 0.0, axis_deg: 0.0 });
+    /// let b = PowerVector::from(SpheroCyl { sphere: -2.5, cylinder: 0.0, axis_deg: 0.0 });
+    /// assert_abs_diff_eq!(a.distance(&b), 0.5, epsilon = 1e-12);
+    /// ```
+    pub fn distance(&self, other: &PowerVector) -> f64 {
+        (*self - *other).magnitude()
+    }
+
+    /// Returns the vector multiplied by a scalar.
+    pub fn scale(&self, factor: f64) -> PowerVector {
+        PowerVector {
+            m: self.m * factor,
+            j0: self.j0 * factor,
+            j45: self.j45 * factor,
+        }
+    }
+
+    /// Converts back to a minus-cylinder [`SpheroCyl`] with the axis in [0, 180).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let rx = SpheroCyl { sphere: 1.0, cylinder: -2.0, axis_deg: 30.0 };
+    /// let back = PowerVector::from(rx).to_sphero_cyl();
+    /// assert_abs_diff_eq!(back.sphere, 1.0, epsilon = 1e-12);
+    /// assert_abs_diff_eq!(back.cylinder, -2.0, epsilon = 1e-12);
+    /// assert_abs_diff_eq!(back.axis_deg, 30.0, epsilon = 1e-9);
+    /// ```
+    pub fn to_sphero_cyl(&self) -> SpheroCyl {
+        let cylinder = -2.0 * self.j0.hypot(self.j45);
+        let axis_deg = (0.5 * self.j45.atan2(self.j0).to_degrees()).rem_euclid(180.0);
+        SpheroCyl {
+            sphere: self.m - cylinder / 2.0,
+            cylinder,
+            axis_deg,
+        }
+    }
+}
+
+impl From<SpheroCyl> for PowerVector {
+    fn from(rx: SpheroCyl) -> Self {
+        let half_cyl = rx.cylinder / 2.0;
+        let (sin, cos) = (2.0 * rx.axis_deg.to_radians()).sin_cos();
+        PowerVector {
+            m: rx.sphere + half_cyl,
+            j0: -half_cyl * cos,
+            j45: -half_cyl * sin,
+        }
+    }
+}
+
+impl Add for PowerVector {
+    type Output = PowerVector;
+
+    fn add(self, rhs: PowerVector) -> PowerVector {
+        PowerVector {
+            m: self.m + rhs.m,
+            j0: self.j0 + rhs.j0,
+            j45: self.j45 + rhs.j45,
+        }
+    }
+}
+
+impl Sub for PowerVector {
+    type Output = PowerVector;
+
+    fn sub(self, rhs: PowerVector) -> PowerVector {
+        PowerVector {
+            m: self.m - rhs.m,
+            j0: self.j0 - rhs.j0,
+            j45: self.j45 - rhs.j45,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-12;
+
+    #[test]
+    fn plus_and_minus_cylinder_forms_give_the_same_vector() {
+        let minus = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -1.0,
+            axis_deg: 0.0,
+        };
+        let a = PowerVector::from(minus);
+        let b = PowerVector::from(minus.transpose());
+        assert_abs_diff_eq!(a.m, b.m, epsilon = EPS);
+        assert_abs_diff_eq!(a.j0, b.j0, epsilon = EPS);
+        assert_abs_diff_eq!(a.j45, b.j45, epsilon = EPS);
+        assert_abs_diff_eq!(a.m, -1.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.j0, 0.5, epsilon = EPS);
+    }
+
+    #[test]
+    fn sum_matches_crossed_cylinders() {
+        let a = SpheroCyl {
+            sphere: 1.0,
+            cylinder: -1.5,
+            axis_deg: 20.0,
+        };
+        let b = SpheroCyl {
+            sphere: -0.5,
+            cylinder: -0.75,
+            axis_deg: 110.0,
+        };
+        let sum = (PowerVector::from(a) + PowerVector::from(b)).to_sphero_cyl();
+        let expected = crossed_cylinders(a, b);
+        assert_abs_diff_eq!(sum.sphere, expected.sphere, epsilon = 1e-9);
+        assert_abs_diff_eq!(sum.cylinder, expected.cylinder, epsilon = 1e-9);
+        assert_abs_diff_eq!(sum.axis_deg, expected.axis_deg, epsilon = 1e-6);
+    }
+}
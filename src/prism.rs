@@ -102,6 +102,100 @@ pub fn induced_prism(eye: Eye, lens: SpheroCyl, dec: Decentration) -> CombinedPr
     }
 }
 
+/// Compute the decentration that produces a prescribed prism, the inverse of [`induced_prism`].
+///
+/// The returned [`Decentration`] uses the same conventions as [`induced_prism`]:
+/// the optical center is moved by this amount relative to the pupil.
+///
+/// A lens with power in one principal meridian only, such as a plano-cylinder,
+/// can only produce prism along that meridian and is decentered along it.
+/// Returns `None` if the prism has a component in a meridian without power;
+/// such prism has to be ground rather than decentered.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // +3.00 DS needs 5 mm of in-decentration for 1.5Δ base in
+/// let lens = SpheroCyl { sphere: 3.0, cylinder: 0.0, axis_deg: 0.0 };
+/// let prism = CombinedPrism {
+///     horizontal: HorizontalPrism::new(1.5, HorizontalBase::In),
+///     vertical: VerticalPrism::new(0.0, VerticalBase::Up),
+/// };
+/// let dec = decentration_for_prism(Eye::OD, lens, prism).unwrap();
+/// assert_abs_diff_eq!(dec.horizontal_mm, 5.0, epsilon = 1e-9);
+/// assert_abs_diff_eq!(dec.vertical_mm, 0.0, epsilon = 1e-9);
+/// ```
+pub fn decentration_for_prism(
+    eye: Eye,
+    lens: SpheroCyl,
+    prism: CombinedPrism,
+) -> Option<Decentration> {
+    let s = lens.sphere;
+    let c = lens.cylinder;
+    let axis_rad = lens.axis_deg.to_radians();
+    let sin_axis = axis_rad.sin();
+    let cos_axis = axis_rad.cos();
+
+    let px = s + c * sin_axis * sin_axis;
+    let pt = -c * sin_axis * cos_axis;
+    let py = s + c * cos_axis * cos_axis;
+
+    // Undo the eye-dependent sign of the horizontal component in `induced_prism`
+    let horiz_value = match eye {
+        Eye::OD => prism.horizontal.signed(),
+        Eye::OS => -prism.horizontal.signed(),
+    };
+    let vert_value = prism.vertical.signed();
+
+    // Invert [h, v] = (1/10)·[[−Px, −Pt], [Pt, Py]]·[in_adj, up]
+    let det = (pt * pt - px * py) / 100.0;
+    let (dec_in_adjusted_mm, dec_up_mm) = if det.abs() >= 1e-12 {
+        (
+            (py * horiz_value + pt * vert_value) / 10.0 / det,
+            (-pt * horiz_value - px * vert_value) / 10.0 / det,
+        )
+    } else {
+        // Singular: [Px, Pt; Pt, Py]·[in_adj, up] = 10·[−h, v] has a solution
+        // only if the target lies along the powered principal meridian
+        let (target_in, target_up) = (-10.0 * horiz_value, 10.0 * vert_value);
+        let power = px + py;
+        let (u_in, u_up) = if px.hypot(pt) >= pt.hypot(py) {
+            (px, pt)
+        } else {
+            (pt, py)
+        };
+        let norm = u_in.hypot(u_up);
+        if norm < 1e-12 {
+            // No power in any meridian
+            if target_in.hypot(target_up) < 1e-9 {
+                (0.0, 0.0)
+            } else {
+                return None;
+            }
+        } else {
+            let (u_in, u_up) = (u_in / norm, u_up / norm);
+            if (target_in * u_up - target_up * u_in).abs() > 1e-9 {
+                return None;
+            }
+            let along = (target_in * u_in + target_up * u_up) / power;
+            (along * u_in, along * u_up)
+        }
+    };
+
+    let dec_in_mm = match eye {
+        Eye::OD => dec_in_adjusted_mm,
+        Eye::OS => -dec_in_adjusted_mm,
+    };
+
+    Some(Decentration {
+        vertical_mm: dec_up_mm,
+        horizontal_mm: dec_in_mm,
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -320,7 +414,7 @@ mod tests {
         assert_abs_diff_eq!(p.horizontal.signed(), 0.5, epsilon = 1e-9);
         assert_abs_diff_eq!(p.vertical.signed(), 0.5, epsilon = 1e-9);
         // Magnitude should be sqrt(0.5^2 + 0.5^2) = ~0.70710678
-        assert_abs_diff_eq!(p.magnitude(), 0.7071067811865476, epsilon = 1e-12);
+        assert_abs_diff_eq!(p.magnitude(), std::f64::consts::FRAC_1_SQRT_2, epsilon = 1e-12);
     }
 
     #[test]
@@ -396,4 +490,78 @@ mod tests {
         assert_abs_diff_eq!(lens.power_at(0.0), px, epsilon = 1e-12);
         assert_abs_diff_eq!(lens.power_at(90.0), py, epsilon = 1e-12);
     }
+
+    #[test]
+    fn decentration_for_prism_round_trips_induced_prism() {
+        let lens = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -3.4,
+            axis_deg: 35.0,
+        };
+        for eye in [Eye::OD, Eye::OS] {
+            let dec = Decentration {
+                horizontal_mm: -1.5,
+                vertical_mm: 2.5,
+            };
+            let prism = induced_prism(eye, lens, dec);
+            let back = decentration_for_prism(eye, lens, prism).unwrap();
+            assert_abs_diff_eq!(back.horizontal_mm, dec.horizontal_mm, epsilon = 1e-9);
+            assert_abs_diff_eq!(back.vertical_mm, dec.vertical_mm, epsilon = 1e-9);
+        }
+    }
+
+    #[test]
+    fn decentration_for_prism_needs_power() {
+        let plano = SpheroCyl {
+            sphere: 0.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let prism = CombinedPrism {
+            horizontal: HorizontalPrism::new(1.0, HorizontalBase::Out),
+            vertical: VerticalPrism::new(0.0, VerticalBase::Up),
+        };
+        assert!(decentration_for_prism(Eye::OD, plano, prism).is_none());
+    }
+
+    #[test]
+    fn decentration_for_prism_along_plano_cylinder_power_meridian() {
+        // 0.00 +2.00 × 90 has power only in the horizontal meridian
+        let lens = SpheroCyl {
+            sphere: 0.0,
+            cylinder: 2.0,
+            axis_deg: 90.0,
+        };
+        let base_in = CombinedPrism {
+            horizontal: HorizontalPrism::new(1.0, HorizontalBase::In),
+            vertical: VerticalPrism::new(0.0, VerticalBase::Up),
+        };
+        for eye in [Eye::OD, Eye::OS] {
+            let dec = decentration_for_prism(eye, lens, base_in).unwrap();
+            assert_abs_diff_eq!(dec.horizontal_mm, 5.0, epsilon = 1e-9);
+            assert_abs_diff_eq!(dec.vertical_mm, 0.0, epsilon = 1e-9);
+        }
+
+        // Oblique plano-cylinder: round trip along the powered 120° meridian
+        let oblique = SpheroCyl {
+            sphere: 0.0,
+            cylinder: -1.5,
+            axis_deg: 30.0,
+        };
+        let dec = Decentration {
+            horizontal_mm: 4.0 * 120f64.to_radians().cos(),
+            vertical_mm: 4.0 * 120f64.to_radians().sin(),
+        };
+        let prism = induced_prism(Eye::OD, oblique, dec);
+        let back = decentration_for_prism(Eye::OD, oblique, prism).unwrap();
+        assert_abs_diff_eq!(back.horizontal_mm, dec.horizontal_mm, epsilon = 1e-9);
+        assert_abs_diff_eq!(back.vertical_mm, dec.vertical_mm, epsilon = 1e-9);
+
+        // Any vertical component needs power in the vertical meridian
+        let with_vertical = CombinedPrism {
+            vertical: VerticalPrism::new(0.5, VerticalBase::Up),
+            ..base_in
+        };
+        assert!(decentration_for_prism(Eye::OD, lens, with_vertical).is_none());
+    }
 }
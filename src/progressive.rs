@@ -0,0 +1,469 @@
+//! Progressive addition lens (PAL) fitting.
+//!
+//! ## Overview
+//! [`fit_progressive`] checks whether a frame can take a progressive design
+//! at the measured fitting heights and locates the near reference point
+//! (NRP) of each lens. The near zone is inset from the fitting cross to
+//! follow the eyes' convergence at the working distance.
+//!
+//! ## Formulas
+//! ```text
+//! Inset:      i = PD_mono · d / (w + d),   d = vertex + 13.5 mm (lens to center of rotation)
+//! Near PD:    PD_near = PD_mono − i
+//! NRP:        fitting cross + (inset nasally, corridor length down)
+//! Clearance:  NRP height above the lens edge directly below it
+//! Required:   minimum fitting height − corridor length
+//! ```
+//!
+//! ## Conventions
+//! - Positions are relative to the boxing center, positive in (nasal) and up,
+//!   as for [`Decentration`].
+//! - Fitting heights are measured from the lowest point of the box (mm).
+//! - The working distance is measured from the spectacle plane, and the
+//!   prismatic effect of the lens on convergence is ignored.
+//! - Without a trace the lens edge below the NRP is the bottom of the box.
+
+use crate::*;
+
+/// Depth of the eye's center of rotation behind the cornea (mm).
+const CENTER_OF_ROTATION_DEPTH_MM: f64 = 13.5;
+
+/// Largest difference between a fixed design inset and the required inset (mm).
+pub const INSET_TOLERANCE_MM: f64 = 1.0;
+
+/// Description of a progressive lens design.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ProgressiveDesign {
+    /// Vertical distance from the fitting cross to the near reference point (mm).
+    ///
+    /// Example: short-corridor design → `corridor_length_mm = 14.0`
+    pub corridor_length_mm: f64,
+
+    /// Smallest fitting height the design accepts (mm).
+    ///
+    /// Example: 18 mm minimum fitting height → `minimum_fitting_height_mm = 18.0`
+    pub minimum_fitting_height_mm: f64,
+
+    /// Nasal inset of the near zone built into the design (mm).
+    /// `None` for designs that set the inset from the patient's convergence.
+    ///
+    /// Example: fixed 2.5 mm inset → `near_inset_mm = Some(2.5)`
+    pub near_inset_mm: Option<f64>,
+}
+
+/// Measured centration of one eye for a progressive lens.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ProgressiveCentration {
+    /// Monocular distance PD, from the frame's bridge center to the pupil (mm).
+    pub monocular_pd_mm: f64,
+
+    /// Height of the fitting cross above the lowest point of the box (mm).
+    pub fitting_height_mm: f64,
+}
+
+/// Viewing conditions for near work.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct NearViewing {
+    /// Vertex distance (mm).
+    ///
+    /// Example: 12 mm vertex → `vertex_distance_mm = 12.0`
+    pub vertex_distance_mm: f64,
+
+    /// Distance from the spectacle plane to the near object (mm).
+    ///
+    /// Example: reading at 40 cm → `working_distance_mm = 400.0`
+    pub working_distance_mm: f64,
+}
+
+impl Default for NearViewing {
+    /// 12 mm vertex distance, reading at 40 cm.
+    fn default() -> Self {
+        Self {
+            vertex_distance_mm: 12.0,
+            working_distance_mm: 400.0,
+        }
+    }
+}
+
+/// A problem with a progressive fit.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum ProgressiveFitIssue {
+    /// The fitting height is below the design's minimum.
+    BelowMinimumFittingHeight,
+
+    /// The fitting cross is not inside the box height.
+    FittingHeightOutsideFrame,
+
+    /// The lens edge leaves less room below the NRP than the design needs.
+    NearZoneCutOff,
+
+    /// The design's fixed inset differs from the required inset by more than
+    /// [`INSET_TOLERANCE_MM`].
+    InsetMismatch,
+}
+
+/// Progressive fit of one lens.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ProgressiveEyeFit {
+    /// Fitting cross relative to the boxing center.
+    pub fitting_cross: Decentration,
+
+    /// Near reference point relative to the boxing center.
+    pub near_reference_point: Decentration,
+
+    /// Inset the eye needs at the working distance (mm).
+    pub required_inset_mm: f64,
+
+    /// Monocular near PD (mm).
+    pub near_monocular_pd_mm: f64,
+
+    /// Height of the NRP above the lens edge directly below it (mm).
+    /// Negative if the NRP is below the edge, 0 if it lies beside the shape.
+    pub clearance_below_nrp_mm: f64,
+
+    /// Problems found; empty when the fit is acceptable.
+    pub issues: Vec<ProgressiveFitIssue>,
+}
+
+/// Progressive fit of a pair of lenses.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ProgressiveFit {
+    /// Right lens.
+    pub od: ProgressiveEyeFit,
+
+    /// Left lens.
+    pub os: ProgressiveEyeFit,
+}
+
+impl ProgressiveFit {
+    /// Returns the binocular near PD (mm).
+    pub fn near_pd_mm(&self) -> f64 {
+        self.od.near_monocular_pd_mm + self.os.near_monocular_pd_mm
+    }
+
+    /// Returns `true` if neither lens has a problem.
+    pub fn passed(&self) -> bool {
+        self.od.issues.is_empty() && self.os.issues.is_empty()
+    }
+}
+
+/// Required nasal inset of the near zone for one eye (mm).
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // 32 mm monocular PD at 12 mm vertex reading at 40 cm: 32 · 25.5 / 425.5
+/// let inset = near_inset_mm(32.0, NearViewing::default());
+/// assert_abs_diff_eq!(inset, 1.918, epsilon = 1e-3);
+/// ```
+///
+/// # Panics
+/// Panics in debug builds if the working distance is not positive.
+pub fn near_inset_mm(monocular_pd_mm: f64, viewing: NearViewing) -> f64 {
+    debug_assert!(
+        viewing.working_distance_mm > 0.0,
+        "working_distance_mm must be positive"
+    );
+
+    let rotation_mm = viewing.vertex_distance_mm + CENTER_OF_ROTATION_DEPTH_MM;
+    monocular_pd_mm * rotation_mm / (viewing.working_distance_mm + rotation_mm)
+}
+
+/// Height of a point above the trace edge directly below it, in trace coordinates.
+fn clearance_above_edge(trace: &FrameTrace, x_mm: f64, y_mm: f64) -> f64 {
+    let crossings: Vec<f64> = trace
+        .points
+        .iter()
+        .zip(trace.points.iter().cycle().skip(1))
+        .filter_map(|(p, q)| {
+            let (lo, hi) = if p.x_mm <= q.x_mm { (p, q) } else { (q, p) };
+            if x_mm < lo.x_mm || x_mm > hi.x_mm || lo.x_mm == hi.x_mm {
+                return None;
+            }
+            let t = (x_mm - lo.x_mm) / (hi.x_mm - lo.x_mm);
+            Some(lo.y_mm + t * (hi.y_mm - lo.y_mm))
+        })
+        .collect();
+
+    let below = crossings
+        .iter()
+        .copied()
+        .filter(|&y| y <= y_mm)
+        .reduce(f64::max);
+    match below {
+        Some(edge) => y_mm - edge,
+        // The point is below the shape: negative clearance
+        None => crossings
+            .iter()
+            .copied()
+            .reduce(f64::min)
+            .map_or(0.0, |edge| y_mm - edge),
+    }
+}
+
+fn fit_eye(
+    eye: Eye,
+    frame: FrameMeasurements,
+    trace: Option<&FrameTrace>,
+    design: ProgressiveDesign,
+    centration: ProgressiveCentration,
+    viewing: NearViewing,
+) -> ProgressiveEyeFit {
+    let fitting_cross = Decentration {
+        horizontal_mm: frame.frame_pd_mm() / 2.0 - centration.monocular_pd_mm,
+        vertical_mm: centration.fitting_height_mm - frame.boxing_center_height_mm(),
+    };
+    let required_inset_mm = near_inset_mm(centration.monocular_pd_mm, viewing);
+    let inset_mm = design.near_inset_mm.unwrap_or(required_inset_mm);
+    let near_reference_point = Decentration {
+        horizontal_mm: fitting_cross.horizontal_mm + inset_mm,
+        vertical_mm: fitting_cross.vertical_mm - design.corridor_length_mm,
+    };
+
+    let clearance_below_nrp_mm = match trace {
+        Some(trace) => {
+            // The trace may belong to the other eye
+            let trace = if trace.eye == eye {
+                trace.clone()
+            } else {
+                trace.mirrored()
+            };
+            let center = trace.geometric_center();
+            let nasal = match eye {
+                Eye::OD => 1.0,
+                Eye::OS => -1.0,
+            };
+            clearance_above_edge(
+                &trace,
+                center.x_mm + nasal * near_reference_point.horizontal_mm,
+                center.y_mm + near_reference_point.vertical_mm,
+            )
+        }
+        None => near_reference_point.vertical_mm + frame.boxing_center_height_mm(),
+    };
+
+    let mut issues = Vec::new();
+    if centration.fitting_height_mm < design.minimum_fitting_height_mm {
+        issues.push(ProgressiveFitIssue::BelowMinimumFittingHeight);
+    }
+    if centration.fitting_height_mm <= 0.0 || centration.fitting_height_mm >= frame.b_mm {
+        issues.push(ProgressiveFitIssue::FittingHeightOutsideFrame);
+    }
+    if clearance_below_nrp_mm < design.minimum_fitting_height_mm - design.corridor_length_mm {
+        issues.push(ProgressiveFitIssue::NearZoneCutOff);
+    }
+    if design
+        .near_inset_mm
+        .is_some_and(|inset| (inset - required_inset_mm).abs() > INSET_TOLERANCE_MM)
+    {
+        issues.push(ProgressiveFitIssue::InsetMismatch);
+    }
+
+    ProgressiveEyeFit {
+        fitting_cross,
+        near_reference_point,
+        required_inset_mm,
+        near_monocular_pd_mm: centration.monocular_pd_mm - required_inset_mm,
+        clearance_below_nrp_mm,
+        issues,
+    }
+}
+
+/// Check a progressive design in a frame and locate each lens's near reference point.
+///
+/// `trace` is the lens shape of either eye; it is mirrored for the other.
+/// Without a trace the bottom of the box limits the near zone.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let frame = FrameMeasurements { a_mm: 52.0, b_mm: 34.0, dbl_mm: 18.0, ed_mm: 56.0, ed_axis_deg: None };
+/// let design = ProgressiveDesign {
+///     corridor_length_mm: 14.0,
+///     minimum_fitting_height_mm: 18.0,
+///     near_inset_mm: None,
+/// };
+/// let eye = ProgressiveCentration { monocular_pd_mm: 32.0, fitting_height_mm: 20.0 };
+///
+/// let fit = fit_progressive(frame, None, design, eye, eye, NearViewing::default());
+/// assert!(fit.passed());
+/// // Fitting cross 3 mm in and 3 mm up; NRP 1.9 mm further in and 14 mm down
+/// assert_abs_diff_eq!(fit.od.near_reference_point.horizontal_mm, 4.918, epsilon = 1e-3);
+/// assert_abs_diff_eq!(fit.od.near_reference_point.vertical_mm, -11.0, epsilon = 1e-12);
+/// assert_abs_diff_eq!(fit.near_pd_mm(), 60.16, epsilon = 1e-2);
+/// ```
+pub fn fit_progressive(
+    frame: FrameMeasurements,
+    trace: Option<&FrameTrace>,
+    design: ProgressiveDesign,
+    od: ProgressiveCentration,
+    os: ProgressiveCentration,
+    viewing: NearViewing,
+) -> ProgressiveFit {
+    ProgressiveFit {
+        od: fit_eye(Eye::OD, frame, trace, design, od, viewing),
+        os: fit_eye(Eye::OS, frame, trace, design, os, viewing),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn short_fitting_height_is_flagged() {
+        let frame = FrameMeasurements {
+            a_mm: 50.0,
+            b_mm: 30.0,
+            dbl_mm: 18.0,
+            ed_mm: 54.0,
+            ed_axis_deg: None,
+        };
+        let design = ProgressiveDesign {
+            corridor_length_mm: 15.0,
+            minimum_fitting_height_mm: 18.0,
+            near_inset_mm: Some(2.5),
+        };
+        let od = ProgressiveCentration {
+            monocular_pd_mm: 31.0,
+            fitting_height_mm: 16.0,
+        };
+        let os = ProgressiveCentration {
+            fitting_height_mm: 19.0,
+            ..od
+        };
+        let fit = fit_progressive(frame, None, design, od, os, NearViewing::default());
+        assert_eq!(
+            fit.od.issues,
+            vec![
+                ProgressiveFitIssue::BelowMinimumFittingHeight,
+                ProgressiveFitIssue::NearZoneCutOff
+            ]
+        );
+        assert!(fit.os.issues.is_empty());
+        assert_abs_diff_eq!(fit.os.clearance_below_nrp_mm, 4.0, epsilon = 1e-12);
+        assert!(!fit.passed());
+    }
+
+    #[test]
+    fn trace_limits_the_near_zone() {
+        // OD shape whose lower nasal corner is cut away: the bottom edge rises
+        // from y = −15 temporally to y = −5 at the nasal side
+        let points = [
+            (25.0, -5.0),
+            (25.0, 15.0),
+            (-25.0, 15.0),
+            (-25.0, -15.0),
+            (0.0, -15.0),
+        ];
+        let trace = FrameTrace::from_points(
+            Eye::OD,
+            points
+                .iter()
+                .map(|&(x_mm, y_mm)| TracePoint { x_mm, y_mm })
+                .collect(),
+        )
+        .unwrap();
+        let frame = FrameMeasurements {
+            a_mm: 50.0,
+            b_mm: 30.0,
+            dbl_mm: 18.0,
+            ed_mm: 54.0,
+            ed_axis_deg: None,
+        };
+        let design = ProgressiveDesign {
+            corridor_length_mm: 15.0,
+            minimum_fitting_height_mm: 18.0,
+            near_inset_mm: Some(2.5),
+        };
+        let eye = ProgressiveCentration {
+            monocular_pd_mm: 31.0,
+            fitting_height_mm: 20.0,
+        };
+        let boxed = fit_progressive(frame, None, design, eye, eye, NearViewing::default());
+        let traced = fit_progressive(
+            frame,
+            Some(&trace),
+            design,
+            eye,
+            eye,
+            NearViewing::default(),
+        );
+
+        // Fitting cross 3 mm in, NRP 5.5 mm in: the edge below is at y = −15 + 10·5.5/25
+        assert_abs_diff_eq!(boxed.od.clearance_below_nrp_mm, 5.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(
+            traced.od.clearance_below_nrp_mm,
+            5.0 - 10.0 * 5.5 / 25.0,
+            epsilon = 1e-12
+        );
+        assert!(
+            traced
+                .od
+                .issues
+                .contains(&ProgressiveFitIssue::NearZoneCutOff)
+        );
+        // The mirrored shape gives the left lens the same clearance
+        assert_abs_diff_eq!(
+            traced.os.clearance_below_nrp_mm,
+            traced.od.clearance_below_nrp_mm,
+            epsilon = 1e-12
+        );
+    }
+
+    #[test]
+    fn fixed_inset_is_checked_against_convergence() {
+        let viewing = NearViewing {
+            vertex_distance_mm: 12.0,
+            working_distance_mm: 1000.0,
+        };
+        let frame = FrameMeasurements {
+            a_mm: 50.0,
+            b_mm: 30.0,
+            dbl_mm: 18.0,
+            ed_mm: 54.0,
+            ed_axis_deg: None,
+        };
+        let design = ProgressiveDesign {
+            corridor_length_mm: 15.0,
+            minimum_fitting_height_mm: 18.0,
+            near_inset_mm: Some(2.5),
+        };
+        let eye = ProgressiveCentration {
+            monocular_pd_mm: 31.0,
+            fitting_height_mm: 20.0,
+        };
+        let fit = fit_progressive(frame, None, design, eye, eye, viewing);
+        // At 1 m the eye needs only 0.77 mm of inset
+        assert_abs_diff_eq!(
+            fit.od.required_inset_mm,
+            31.0 * 25.5 / 1025.5,
+            epsilon = 1e-12
+        );
+        assert!(fit.od.issues.contains(&ProgressiveFitIssue::InsetMismatch));
+    }
+}
@@ -0,0 +1,391 @@
+//! Surface reflectance and anti-reflection coatings.
+//!
+//! ## Overview
+//! - [`fresnel_reflectance`] and [`surface_reflectance`]: reflectance of one
+//!   uncoated surface at normal incidence.
+//! - [`uncoated_transmittance`]: transmittance of an uncoated lens, counting
+//!   both surfaces and the light bouncing between them.
+//! - [`thin_film_reflectance`]: reflectance of a surface with a single- or
+//!   multi-layer coating, by the characteristic matrix method.
+//! - [`reflectance_spectrum`] and [`luminous_reflectance`]: the visible
+//!   spectrum (380–780 nm) and its photopic weighting.
+//!
+//! ## Formulas
+//! ```text
+//! Fresnel:         R = ((n₁ − n₂) / (n₁ + n₂))²
+//! Uncoated lens:   T = (1 − R)² / (1 − R²) = (1 − R) / (1 + R)
+//! Layer matrix:    δ = 2π n d / λ
+//!                  M = | cos δ        i sin δ / n |
+//!                      | i n sin δ    cos δ       |
+//! Stack:           [B, C]ᵀ = M₁ M₂ … M_k [1, n_s]ᵀ
+//!                  r = (n₀B − C) / (n₀B + C),  R = |r|²
+//! Luminous:        R_v = Σ R(λ) V(λ) / Σ V(λ)
+//! ```
+//!
+//! ## Conventions
+//! - Light arrives from air (n₀ = 1) at normal incidence.
+//! - Layers are listed in the order light meets them, from air to substrate.
+//! - Layer materials are lossless; absorption in the lens is ignored.
+//! - V(λ) is the CIE 1924 photopic luminosity function.
+
+use std::ops::{Add, Mul, Sub};
+
+/// First wavelength of the visible spectrum used here (nm).
+pub const VISIBLE_START_NM: f64 = 380.0;
+
+/// Last wavelength of the visible spectrum used here (nm).
+pub const VISIBLE_END_NM: f64 = 780.0;
+
+/// Step between tabulated visible wavelengths (nm).
+pub const VISIBLE_STEP_NM: f64 = 10.0;
+
+/// CIE 1924 photopic luminosity function V(λ), 380–780 nm in 10 nm steps.
+pub const PHOTOPIC_LUMINOSITY: [f64; 41] = [
+    0.000039, 0.000120, 0.000396, 0.001210, 0.004000, 0.011600, 0.023000, 0.038000, 0.060000,
+    0.090980, 0.139020, 0.208020, 0.323000, 0.503000, 0.710000, 0.862000, 0.954000, 0.994950,
+    0.995000, 0.952000, 0.870000, 0.757000, 0.631000, 0.503000, 0.381000, 0.265000, 0.175000,
+    0.107000, 0.061000, 0.032000, 0.017000, 0.008210, 0.004102, 0.002091, 0.001047, 0.000520,
+    0.000249, 0.000120, 0.000060, 0.000030, 0.000015,
+];
+
+/// One layer of a thin-film coating.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ThinFilmLayer {
+    /// Refractive index of the layer material.
+    ///
+    /// Example: magnesium fluoride → `index = 1.38`
+    pub index: f64,
+
+    /// Physical thickness (nm).
+    ///
+    /// Example: quarter-wave MgF₂ at 550 nm → `thickness_nm = 99.6`
+    pub thickness_nm: f64,
+}
+
+impl ThinFilmLayer {
+    /// Creates a layer whose optical thickness is a quarter of `wavelength_nm`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let mgf2 = ThinFilmLayer::quarter_wave(1.38, 550.0);
+    /// assert_abs_diff_eq!(mgf2.thickness_nm, 99.64, epsilon = 0.01);
+    /// ```
+    pub fn quarter_wave(index: f64, wavelength_nm: f64) -> Self {
+        Self {
+            index,
+            thickness_nm: wavelength_nm / (4.0 * index),
+        }
+    }
+}
+
+/// Reflectance at one wavelength.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SpectralReflectance {
+    /// Wavelength (nm).
+    pub wavelength_nm: f64,
+
+    /// Reflectance (fraction, 0–1).
+    pub reflectance: f64,
+}
+
+/// Compute the Fresnel reflectance of a boundary at normal incidence (fraction).
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// assert_abs_diff_eq!(fresnel_reflectance(1.0, 1.5), 0.04, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if either index is not positive.
+pub fn fresnel_reflectance(n1: f64, n2: f64) -> f64 {
+    assert!(n1 > 0.0 && n2 > 0.0, "indices must be positive");
+    ((n1 - n2) / (n1 + n2)).powi(2)
+}
+
+/// Compute the reflectance of one uncoated lens surface in air (fraction).
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// // 1.74 reflects almost twice as much as CR-39
+/// assert!(surface_reflectance(HIGH_INDEX_174_INDEX) > 1.8 * surface_reflectance(CR_39_INDEX));
+/// ```
+pub fn surface_reflectance(index: f64) -> f64 {
+    fresnel_reflectance(1.0, index)
+}
+
+/// Compute the transmittance of an uncoated lens in air (fraction).
+///
+/// Both surfaces are counted, including light reflected back and forth
+/// between them. Absorption in the lens is ignored.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // n = 1.5: 4% per surface, about 92.3% transmitted
+/// assert_abs_diff_eq!(uncoated_transmittance(1.5), 0.923, epsilon = 1e-3);
+/// ```
+pub fn uncoated_transmittance(index: f64) -> f64 {
+    let r = surface_reflectance(index);
+    (1.0 - r) / (1.0 + r)
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Complex {
+    re: f64,
+    im: f64,
+}
+
+impl Complex {
+    fn new(re: f64, im: f64) -> Self {
+        Self { re, im }
+    }
+
+    fn norm_sqr(self) -> f64 {
+        self.re * self.re + self.im * self.im
+    }
+}
+
+impl Add for Complex {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(self.re + rhs.re, self.im + rhs.im)
+    }
+}
+
+impl Sub for Complex {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(self.re - rhs.re, self.im - rhs.im)
+    }
+}
+
+impl Mul for Complex {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self {
+        Self::new(
+            self.re * rhs.re - self.im * rhs.im,
+            self.re * rhs.im + self.im * rhs.re,
+        )
+    }
+}
+
+/// Compute the reflectance of a coated surface at one wavelength (fraction).
+///
+/// With no layers this is the bare [`surface_reflectance`].
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // A quarter-wave layer of index √1.523 cancels reflection at its design wavelength
+/// let ideal = ThinFilmLayer::quarter_wave(CROWN_GLASS_INDEX.sqrt(), 550.0);
+/// assert_abs_diff_eq!(thin_film_reflectance(CROWN_GLASS_INDEX, &[ideal], 550.0), 0.0, epsilon = 1e-12);
+/// ```
+///
+/// # Panics
+/// Panics if `substrate_index`, `wavelength_nm` or any layer index is not positive.
+pub fn thin_film_reflectance(
+    substrate_index: f64,
+    layers: &[ThinFilmLayer],
+    wavelength_nm: f64,
+) -> f64 {
+    assert!(substrate_index > 0.0, "substrate_index must be positive");
+    assert!(wavelength_nm > 0.0, "wavelength_nm must be positive");
+
+    let zero = Complex::new(0.0, 0.0);
+    let mut m = [
+        [Complex::new(1.0, 0.0), zero],
+        [zero, Complex::new(1.0, 0.0)],
+    ];
+    for layer in layers {
+        assert!(layer.index > 0.0, "layer index must be positive");
+        let delta = 2.0 * std::f64::consts::PI * layer.index * layer.thickness_nm / wavelength_nm;
+        let (sin, cos) = delta.sin_cos();
+        let l = [
+            [Complex::new(cos, 0.0), Complex::new(0.0, sin / layer.index)],
+            [Complex::new(0.0, layer.index * sin), Complex::new(cos, 0.0)],
+        ];
+        m = [
+            [
+                m[0][0] * l[0][0] + m[0][1] * l[1][0],
+                m[0][0] * l[0][1] + m[0][1] * l[1][1],
+            ],
+            [
+                m[1][0] * l[0][0] + m[1][1] * l[1][0],
+                m[1][0] * l[0][1] + m[1][1] * l[1][1],
+            ],
+        ];
+    }
+
+    let ns = Complex::new(substrate_index, 0.0);
+    let b = m[0][0] + m[0][1] * ns;
+    let c = m[1][0] + m[1][1] * ns;
+    let r_num = (b - c).norm_sqr();
+    let r_den = (b + c).norm_sqr();
+    r_num / r_den
+}
+
+/// Compute the reflectance spectrum of a coated surface, 380–780 nm in 10 nm steps.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// let spectrum = reflectance_spectrum(POLYCARBONATE_INDEX, &[]);
+/// assert_eq!(spectrum.len(), 41);
+/// assert_eq!(spectrum[0].wavelength_nm, VISIBLE_START_NM);
+/// ```
+pub fn reflectance_spectrum(
+    substrate_index: f64,
+    layers: &[ThinFilmLayer],
+) -> Vec<SpectralReflectance> {
+    (0..PHOTOPIC_LUMINOSITY.len())
+        .map(|i| {
+            let wavelength_nm = VISIBLE_START_NM + i as f64 * VISIBLE_STEP_NM;
+            SpectralReflectance {
+                wavelength_nm,
+                reflectance: thin_film_reflectance(substrate_index, layers, wavelength_nm),
+            }
+        })
+        .collect()
+}
+
+/// Returns V(λ) by linear interpolation, or 0 outside 380–780 nm.
+pub fn photopic_luminosity(wavelength_nm: f64) -> f64 {
+    if !(VISIBLE_START_NM..=VISIBLE_END_NM).contains(&wavelength_nm) {
+        return 0.0;
+    }
+    let pos = (wavelength_nm - VISIBLE_START_NM) / VISIBLE_STEP_NM;
+    let i = (pos.floor() as usize).min(PHOTOPIC_LUMINOSITY.len() - 2);
+    let t = pos - i as f64;
+    PHOTOPIC_LUMINOSITY[i] * (1.0 - t) + PHOTOPIC_LUMINOSITY[i + 1] * t
+}
+
+/// Compute the photopic luminous reflectance of a spectrum (fraction).
+///
+/// Returns `None` if no sample falls within 380–780 nm.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// let layers = [ThinFilmLayer::quarter_wave(1.38, 550.0)];
+/// let spectrum = reflectance_spectrum(HIGH_INDEX_167_INDEX, &layers);
+/// let rv = luminous_reflectance(&spectrum).unwrap();
+/// assert!(rv < surface_reflectance(HIGH_INDEX_167_INDEX) / 4.0);
+/// ```
+pub fn luminous_reflectance(spectrum: &[SpectralReflectance]) -> Option<f64> {
+    let (weighted, total) = spectrum.iter().fold((0.0, 0.0), |(w, t), s| {
+        let v = photopic_luminosity(s.wavelength_nm);
+        (w + s.reflectance * v, t + v)
+    });
+    (total > 0.0).then(|| weighted / total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    #[test]
+    fn bare_surface_matches_fresnel_at_all_wavelengths() {
+        for s in reflectance_spectrum(HIGH_INDEX_174_INDEX, &[]) {
+            assert_abs_diff_eq!(
+                s.reflectance,
+                surface_reflectance(HIGH_INDEX_174_INDEX),
+                epsilon = EPS
+            );
+        }
+    }
+
+    #[test]
+    fn single_layer_quarter_wave_minimum() {
+        // R = ((n₀nₛ − n₁²) / (n₀nₛ + n₁²))² at the design wavelength
+        let layer = ThinFilmLayer::quarter_wave(1.38, 550.0);
+        let expected = ((1.523_f64 - 1.38 * 1.38) / (1.523 + 1.38 * 1.38)).powi(2);
+        assert_abs_diff_eq!(
+            thin_film_reflectance(1.523, &[layer], 550.0),
+            expected,
+            epsilon = EPS
+        );
+        assert!(thin_film_reflectance(1.523, &[layer], 400.0) > expected);
+    }
+
+    #[test]
+    fn half_wave_layer_is_absentee() {
+        let layer = ThinFilmLayer {
+            index: 2.1,
+            thickness_nm: 550.0 / (2.0 * 2.1),
+        };
+        assert_abs_diff_eq!(
+            thin_film_reflectance(1.6, &[layer], 550.0),
+            surface_reflectance(1.6),
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn quarter_half_quarter_broadband_design() {
+        // Classic three-layer AR: λ/4 MgF₂, λ/2 ZrO₂, λ/4 Al₂O₃ (air to substrate)
+        let layers = [
+            ThinFilmLayer::quarter_wave(1.38, 510.0),
+            ThinFilmLayer {
+                index: 2.05,
+                thickness_nm: 510.0 / (2.0 * 2.05),
+            },
+            ThinFilmLayer::quarter_wave(1.62, 510.0),
+        ];
+        let coated = luminous_reflectance(&reflectance_spectrum(1.523, &layers)).unwrap();
+        let single = luminous_reflectance(&reflectance_spectrum(
+            1.523,
+            &[ThinFilmLayer::quarter_wave(1.38, 510.0)],
+        ))
+        .unwrap();
+        assert!(coated < single);
+        assert!(coated < 0.01);
+    }
+
+    #[test]
+    fn luminous_reflectance_of_flat_spectrum() {
+        let spectrum = [
+            SpectralReflectance {
+                wavelength_nm: 500.0,
+                reflectance: 0.05,
+            },
+            SpectralReflectance {
+                wavelength_nm: 600.0,
+                reflectance: 0.05,
+            },
+        ];
+        assert_abs_diff_eq!(
+            luminous_reflectance(&spectrum).unwrap(),
+            0.05,
+            epsilon = EPS
+        );
+        assert!(luminous_reflectance(&[]).is_none());
+    }
+}
@@ -0,0 +1,493 @@
+//! Statistics over repeated refractions.
+//!
+//! ## Overview
+//! Sphero-cylinders cannot be averaged component by component: the mean of
+//! axes 5° and 175° is 180°, not 90°. Readings are instead converted to
+//! [`PowerVector`]s, where ordinary statistics apply:
+//! - [`refraction_summary`]: mean Rx, covariance and the 95% astigmatism ellipse.
+//! - [`bland_altman`]: limits of agreement between paired readings of two instruments.
+//! - [`reject_outliers`]: iterative leave-one-out Mahalanobis screening of repeated readings.
+//!
+//! ## Formulas
+//! ```text
+//! Mean:           PV̄ = Σ PVᵢ / n
+//! Covariance:     Σ = Σ (PVᵢ − PV̄)(PVᵢ − PV̄)ᵀ / (n − 1)
+//! Ellipse:        semi-axes √(χ²₂(0.95) · λ), λ eigenvalues of the J0/J45 block
+//! Agreement:      bias ± 1.96 · SD of the paired differences
+//! Mahalanobis:    d² = (PVᵢ − PV̄₋ᵢ)ᵀ Σ₋ᵢ⁻¹ (PVᵢ − PV̄₋ᵢ)
+//! ```
+//!
+//! ## Conventions
+//! - Vector components are ordered `[M, J0, J45]`, in **diopters (D)**.
+//! - The ellipse covers 95% of individual readings, not the mean.
+//! - For outlier screening each reading is compared with the mean and
+//!   covariance of the other remaining readings. The rounding variance of
+//!   0.25 D steps, 0.25²/12 D², is added to each component so that readings
+//!   reported in steps, even identical ones, do not give a singular covariance.
+
+use crate::*;
+
+/// χ² with 2 degrees of freedom at 95%.
+const CHI_SQUARED_2_95: f64 = 5.991;
+
+/// Rounding variance of 0.25 D steps, added to each component for outlier screening (D²).
+const MEASUREMENT_VARIANCE: f64 = 0.25 * 0.25 / 12.0;
+
+/// Mahalanobis distance containing 95% of normal readings in three dimensions,
+/// `√χ²₃(0.95)`.
+pub const MAHALANOBIS_95: f64 = 2.7955;
+
+/// Ellipse in the J0/J45 plane.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AstigmatismEllipse {
+    /// Center J0 (D).
+    pub center_j0: f64,
+
+    /// Center J45 (D).
+    pub center_j45: f64,
+
+    /// Semi-major axis (D).
+    pub semi_major: f64,
+
+    /// Semi-minor axis (D).
+    pub semi_minor: f64,
+
+    /// Angle of the major axis from the J0 axis (degrees), in (−90, 90].
+    pub angle_deg: f64,
+}
+
+/// Mean and spread of a set of refractions.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RefractionSummary {
+    /// Number of readings.
+    pub count: usize,
+
+    /// Mean Rx, minus-cylinder form.
+    pub mean: SpheroCyl,
+
+    /// Mean power vector.
+    pub mean_vector: PowerVector,
+
+    /// Sample covariance of `[M, J0, J45]` (D²), zero for a single reading.
+    pub covariance: [[f64; 3]; 3],
+
+    /// 95% ellipse of the readings in the J0/J45 plane.
+    pub ellipse: AstigmatismEllipse,
+}
+
+/// Bland–Altman agreement for one quantity.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct LimitsOfAgreement {
+    /// Mean difference, first minus second (D).
+    pub bias: f64,
+
+    /// Sample standard deviation of the differences (D).
+    pub sd: f64,
+
+    /// Lower 95% limit of agreement, `bias − 1.96·SD` (D).
+    pub lower: f64,
+
+    /// Upper 95% limit of agreement, `bias + 1.96·SD` (D).
+    pub upper: f64,
+}
+
+/// Bland–Altman agreement between two instruments in power-vector components.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PowerVectorAgreement {
+    /// Number of pairs.
+    pub count: usize,
+
+    /// Agreement of the spherical equivalent.
+    pub m: LimitsOfAgreement,
+
+    /// Agreement of J0.
+    pub j0: LimitsOfAgreement,
+
+    /// Agreement of J45.
+    pub j45: LimitsOfAgreement,
+}
+
+/// Result of outlier screening.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct OutlierScreening {
+    /// Readings within the cutoff, in their original order.
+    pub kept: Vec<SpheroCyl>,
+
+    /// Indices of the rejected readings.
+    pub rejected: Vec<usize>,
+
+    /// Leave-one-out Mahalanobis distance of every reading.
+    pub distances: Vec<f64>,
+}
+
+fn components(v: PowerVector) -> [f64; 3] {
+    [v.m, v.j0, v.j45]
+}
+
+/// Returns the mean vector and sample covariance of a non-empty set.
+fn mean_covariance(vectors: &[[f64; 3]]) -> ([f64; 3], [[f64; 3]; 3]) {
+    let n = vectors.len() as f64;
+    let mut mean = [0.0; 3];
+    for v in vectors {
+        for i in 0..3 {
+            mean[i] += v[i] / n;
+        }
+    }
+    let mut covariance = [[0.0; 3]; 3];
+    if vectors.len() > 1 {
+        for v in vectors {
+            for i in 0..3 {
+                for j in 0..3 {
+                    covariance[i][j] += (v[i] - mean[i]) * (v[j] - mean[j]) / (n - 1.0);
+                }
+            }
+        }
+    }
+    (mean, covariance)
+}
+
+/// Inverse of a symmetric 3×3 matrix, or `None` if it is singular.
+fn invert(m: [[f64; 3]; 3]) -> Option<[[f64; 3]; 3]> {
+    let cofactor = |i: usize, j: usize| {
+        let (r0, r1) = ((i + 1) % 3, (i + 2) % 3);
+        let (c0, c1) = ((j + 1) % 3, (j + 2) % 3);
+        m[r0][c0] * m[r1][c1] - m[r0][c1] * m[r1][c0]
+    };
+    let determinant = (0..3).map(|j| m[0][j] * cofactor(0, j)).sum::<f64>();
+    if determinant.abs() < f64::MIN_POSITIVE {
+        return None;
+    }
+    let mut inverse = [[0.0; 3]; 3];
+    for (i, row) in inverse.iter_mut().enumerate() {
+        for (j, value) in row.iter_mut().enumerate() {
+            *value = cofactor(j, i) / determinant;
+        }
+    }
+    Some(inverse)
+}
+
+/// Summarize a set of refractions in power-vector space.
+///
+/// Returns `None` if `readings` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // Axes either side of 180° average to 180°, not 90°
+/// let readings = [
+///     SpheroCyl { sphere: -1.0, cylinder: -1.0, axis_deg: 5.0 },
+///     SpheroCyl { sphere: -1.0, cylinder: -1.0, axis_deg: 175.0 },
+/// ];
+/// let summary = refraction_summary(&readings).unwrap();
+/// assert_abs_diff_eq!(summary.mean.axis_deg.min(180.0 - summary.mean.axis_deg), 0.0, epsilon = 1e-9);
+/// assert!(summary.mean.cylinder > -1.0); // some cylinder cancels
+/// ```
+pub fn refraction_summary(readings: &[SpheroCyl]) -> Option<RefractionSummary> {
+    if readings.is_empty() {
+        return None;
+    }
+    let vectors: Vec<[f64; 3]> = readings
+        .iter()
+        .map(|&rx| components(PowerVector::from(rx)))
+        .collect();
+    let (mean, covariance) = mean_covariance(&vectors);
+    let mean_vector = PowerVector {
+        m: mean[0],
+        j0: mean[1],
+        j45: mean[2],
+    };
+
+    // Eigen decomposition of the J0/J45 block
+    let (a, b, c) = (covariance[1][1], covariance[1][2], covariance[2][2]);
+    let half_trace = (a + c) / 2.0;
+    let spread = ((a - c) / 2.0).hypot(b);
+    let major = (half_trace + spread).max(0.0);
+    let minor = (half_trace - spread).max(0.0);
+    let angle_deg = 0.5 * (2.0 * b).atan2(a - c).to_degrees();
+
+    Some(RefractionSummary {
+        count: readings.len(),
+        mean: mean_vector.to_sphero_cyl(),
+        mean_vector,
+        covariance,
+        ellipse: AstigmatismEllipse {
+            center_j0: mean_vector.j0,
+            center_j45: mean_vector.j45,
+            semi_major: (CHI_SQUARED_2_95 * major).sqrt(),
+            semi_minor: (CHI_SQUARED_2_95 * minor).sqrt(),
+            angle_deg,
+        },
+    })
+}
+
+/// Bland–Altman limits of agreement between paired readings.
+///
+/// Differences are `first − second` for each power-vector component.
+/// Returns `None` if the slices are empty or of different lengths.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let rx = |sphere| SpheroCyl { sphere, cylinder: -0.5, axis_deg: 90.0 };
+/// let autorefractor = [rx(-2.0), rx(-1.0), rx(0.5), rx(1.25)];
+/// let subjective = [rx(-1.75), rx(-0.75), rx(0.75), rx(1.5)];
+///
+/// let agreement = bland_altman(&autorefractor, &subjective).unwrap();
+/// assert_abs_diff_eq!(agreement.m.bias, -0.25, epsilon = 1e-12);
+/// assert_abs_diff_eq!(agreement.m.upper - agreement.m.lower, 0.0, epsilon = 1e-12);
+/// ```
+pub fn bland_altman(first: &[SpheroCyl], second: &[SpheroCyl]) -> Option<PowerVectorAgreement> {
+    if first.is_empty() || first.len() != second.len() {
+        return None;
+    }
+    let differences: Vec<[f64; 3]> = first
+        .iter()
+        .zip(second)
+        .map(|(&a, &b)| components(PowerVector::from(a) - PowerVector::from(b)))
+        .collect();
+    let (mean, covariance) = mean_covariance(&differences);
+    let limits = |i: usize| {
+        let sd = covariance[i][i].sqrt();
+        LimitsOfAgreement {
+            bias: mean[i],
+            sd,
+            lower: mean[i] - 1.96 * sd,
+            upper: mean[i] + 1.96 * sd,
+        }
+    };
+
+    Some(PowerVectorAgreement {
+        count: first.len(),
+        m: limits(0),
+        j0: limits(1),
+        j45: limits(2),
+    })
+}
+
+/// Mahalanobis distance of `vectors[i]` from the other `active` vectors.
+fn leave_one_out_distance(vectors: &[[f64; 3]], active: &[usize], i: usize) -> f64 {
+    let others: Vec<[f64; 3]> = active
+        .iter()
+        .filter(|&&j| j != i)
+        .map(|&j| vectors[j])
+        .collect();
+    let (mean, mut covariance) = mean_covariance(&others);
+    for (k, row) in covariance.iter_mut().enumerate() {
+        row[k] += MEASUREMENT_VARIANCE;
+    }
+    let Some(inverse) = invert(covariance) else {
+        return f64::INFINITY;
+    };
+    let d = [
+        vectors[i][0] - mean[0],
+        vectors[i][1] - mean[1],
+        vectors[i][2] - mean[2],
+    ];
+    let mut squared = 0.0;
+    for r in 0..3 {
+        for c in 0..3 {
+            squared += d[r] * inverse[r][c] * d[c];
+        }
+    }
+    squared.max(0.0).sqrt()
+}
+
+/// Screen repeated readings for outliers by Mahalanobis distance.
+///
+/// Each reading is compared with the mean and covariance of the other
+/// remaining readings. The most distant reading beyond `cutoff` (see
+/// [`MAHALANOBIS_95`]) is rejected and the screening repeats, so one gross
+/// outlier cannot distort the covariance used to judge the rest. Screening
+/// stops when fewer than three readings remain.
+///
+/// `distances` holds each reading's distance when it was rejected, or in the
+/// final pass if it was kept; with fewer than three readings all are 0.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let rx = |sphere, axis_deg| SpheroCyl { sphere, cylinder: -0.75, axis_deg };
+/// let readings = [
+///     rx(-2.0, 10.0), rx(-2.25, 12.0), rx(-2.0, 8.0),
+///     rx(-2.25, 10.0), rx(-2.0, 11.0), rx(-0.5, 95.0),
+/// ];
+/// let screening = reject_outliers(&readings, MAHALANOBIS_95);
+/// assert_eq!(screening.rejected, vec![5]);
+/// assert_eq!(screening.kept.len(), 5);
+/// ```
+pub fn reject_outliers(readings: &[SpheroCyl], cutoff: f64) -> OutlierScreening {
+    let vectors: Vec<[f64; 3]> = readings
+        .iter()
+        .map(|&rx| components(PowerVector::from(rx)))
+        .collect();
+
+    let mut distances = vec![0.0; vectors.len()];
+    let mut active: Vec<usize> = (0..vectors.len()).collect();
+    let mut rejected = Vec::new();
+    while active.len() >= 3 {
+        for &i in &active {
+            distances[i] = leave_one_out_distance(&vectors, &active, i);
+        }
+        let (position, &worst) = active
+            .iter()
+            .enumerate()
+            .max_by(|a, b| distances[*a.1].total_cmp(&distances[*b.1]))
+            .expect("at least three readings remain");
+        if distances[worst] <= cutoff {
+            break;
+        }
+        rejected.push(worst);
+        active.remove(position);
+    }
+    rejected.sort_unstable();
+
+    OutlierScreening {
+        kept: active.iter().map(|&i| readings[i]).collect(),
+        rejected,
+        distances,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-12;
+
+    #[test]
+    fn summary_of_spheres_has_no_astigmatism() {
+        let sphere = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let other = SpheroCyl {
+            sphere: -2.0,
+            axis_deg: 90.0,
+            ..sphere
+        };
+        let summary = refraction_summary(&[sphere, other]).unwrap();
+        assert_eq!(summary.count, 2);
+        assert_abs_diff_eq!(summary.mean.sphere, -1.5, epsilon = EPS);
+        assert_abs_diff_eq!(summary.mean.cylinder, 0.0, epsilon = EPS);
+        assert_abs_diff_eq!(summary.covariance[0][0], 0.5, epsilon = EPS);
+        assert_abs_diff_eq!(summary.ellipse.semi_major, 0.0, epsilon = EPS);
+        assert!(refraction_summary(&[]).is_none());
+    }
+
+    #[test]
+    fn ellipse_follows_the_spread_direction() {
+        // Readings differ only in J0: the ellipse lies along the J0 axis
+        let base = SpheroCyl {
+            sphere: 0.0,
+            cylinder: -1.0,
+            axis_deg: 180.0,
+        };
+        let summary = refraction_summary(&[
+            base,
+            SpheroCyl {
+                sphere: 0.5,
+                cylinder: -2.0,
+                ..base
+            },
+            SpheroCyl {
+                sphere: -0.5,
+                cylinder: 0.0,
+                ..base
+            },
+        ])
+        .unwrap();
+        assert_abs_diff_eq!(summary.covariance[1][1], 0.25, epsilon = EPS);
+        assert_abs_diff_eq!(summary.ellipse.angle_deg, 0.0, epsilon = 1e-9);
+        assert_abs_diff_eq!(
+            summary.ellipse.semi_major,
+            (CHI_SQUARED_2_95 * 0.25_f64).sqrt(),
+            epsilon = 1e-9
+        );
+        assert_abs_diff_eq!(summary.ellipse.semi_minor, 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn bland_altman_requires_pairs() {
+        let low = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let high = SpheroCyl {
+            sphere: -2.0,
+            ..low
+        };
+        let a = [low, high];
+        assert!(bland_altman(&a, &a[..1]).is_none());
+        assert!(bland_altman(&[], &[]).is_none());
+        let b = [
+            SpheroCyl {
+                sphere: -1.5,
+                ..low
+            },
+            high,
+        ];
+        let agreement = bland_altman(&a, &b).unwrap();
+        assert_abs_diff_eq!(agreement.m.bias, 0.25, epsilon = EPS);
+        assert_abs_diff_eq!(agreement.m.sd, 0.5_f64.sqrt() / 2.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn few_readings_are_never_rejected() {
+        let myope = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let hyperope = SpheroCyl {
+            sphere: 5.0,
+            ..myope
+        };
+        let screening = reject_outliers(&[myope, hyperope], 1.0);
+        assert!(screening.rejected.is_empty());
+        assert_eq!(screening.distances, vec![0.0, 0.0]);
+    }
+
+    #[test]
+    fn identical_readings_do_not_make_the_covariance_singular() {
+        let reading = SpheroCyl {
+            sphere: -3.0,
+            cylinder: -1.0,
+            axis_deg: 90.0,
+        };
+        let mut readings = vec![reading; 5];
+        readings.push(SpheroCyl {
+            sphere: -6.0,
+            ..reading
+        });
+        let screening = reject_outliers(&readings, MAHALANOBIS_95);
+        assert_eq!(screening.rejected, vec![5]);
+        assert_eq!(screening.kept.len(), 5);
+        assert!(screening.distances.iter().all(|d| d.is_finite()));
+    }
+}
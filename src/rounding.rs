@@ -0,0 +1,350 @@
+//! Rounding prescriptions to clinical step sizes.
+//!
+//! ## Overview
+//! Calculated powers such as `−2.3712 −0.8831 × 37.4` have to be ordered in
+//! standard steps. [`SpheroCyl::rounded`] rounds sphere and cylinder to a
+//! power step, the axis to whole or five degrees, and reports the error the
+//! rounding introduced as a [`PowerVector`].
+//!
+//! ## Steps
+//! ```text
+//! Step025    0.25 D         −2.25, −2.50, …
+//! Step0125   0.125 D        −2.125, −2.25, −2.375, …
+//! Step012    0.125 D, written to two decimals toward zero: 0.12, 0.25, 0.37, …
+//! ```
+//!
+//! ## Conventions
+//! - Sphere and cylinder are rounded in the cylinder form they are given in.
+//! - With `Step012`, a power up to 0.005 D closer to zero than a step (e.g. a
+//!   written 0.37 for 0.375) is already on that step and is kept in every
+//!   rounding mode.
+//! - With `preserve_spherical_equivalent`, the cylinder is rounded first and
+//!   the sphere is chosen to keep `S + C/2` as close to the original as the
+//!   step allows.
+//! - Axes are normalized into the clinical range (0, 180], so whole-degree
+//!   axes fall in [1, 180].
+
+use crate::*;
+
+/// Power step of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum PowerStep {
+    /// 0.25 D steps.
+    Step025,
+
+    /// 0.125 D steps.
+    Step0125,
+
+    /// 0.125 D steps written to two decimals (0.12, 0.37, 0.62, 0.87).
+    Step012,
+}
+
+/// Direction of power rounding.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum RoundingMode {
+    /// Nearest step; halfway values round away from zero.
+    Nearest,
+
+    /// Next step in the plus direction.
+    TowardPlus,
+
+    /// Next step in the minus direction.
+    TowardMinus,
+}
+
+/// Axis step of an order.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum AxisStep {
+    /// Whole degrees.
+    Deg1,
+
+    /// Multiples of 5°.
+    Deg5,
+}
+
+/// Options for [`SpheroCyl::rounded`].
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RoundingOptions {
+    /// Power step for sphere and cylinder.
+    pub power_step: PowerStep,
+
+    /// Direction of power rounding.
+    pub mode: RoundingMode,
+
+    /// Axis step.
+    pub axis_step: AxisStep,
+
+    /// Keep the spherical equivalent as close to the original as possible.
+    pub preserve_spherical_equivalent: bool,
+}
+
+impl Default for RoundingOptions {
+    /// Nearest 0.25 D and whole degree, without preserving the spherical equivalent.
+    fn default() -> Self {
+        Self {
+            power_step: PowerStep::Step025,
+            mode: RoundingMode::Nearest,
+            axis_step: AxisStep::Deg1,
+            preserve_spherical_equivalent: false,
+        }
+    }
+}
+
+/// A rounded prescription and the error rounding introduced.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RoundedRx {
+    /// Rounded prescription.
+    pub rx: SpheroCyl,
+
+    /// Rounded minus original, as a power vector.
+    pub error: PowerVector,
+}
+
+/// Round a power to a step in the given direction (D).
+///
+/// Powers already on a step are returned unchanged. For
+/// [`PowerStep::Step012`] this includes the two-decimal forms, so 0.37 is kept
+/// rather than read as 0.005 D below 0.375.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// assert_eq!(round_power(-2.3712, PowerStep::Step025, RoundingMode::Nearest), -2.25);
+/// assert_eq!(round_power(-2.3712, PowerStep::Step025, RoundingMode::TowardMinus), -2.5);
+/// assert_eq!(round_power(0.3712, PowerStep::Step012, RoundingMode::Nearest), 0.37);
+/// assert_eq!(round_power(0.37, PowerStep::Step012, RoundingMode::TowardMinus), 0.37);
+/// ```
+pub fn round_power(power: f64, step: PowerStep, mode: RoundingMode) -> f64 {
+    let size = match step {
+        PowerStep::Step025 => 0.25,
+        PowerStep::Step0125 | PowerStep::Step012 => 0.125,
+    };
+    let steps = power / size;
+    let nearest = steps.round();
+    let offset = (steps - nearest).abs();
+    // Two-decimal forms are truncated toward zero, up to 0.005 D short of their step
+    let truncated =
+        step == PowerStep::Step012 && nearest.abs() > steps.abs() && offset <= 0.005 / size + 1e-9;
+    let steps = if offset < 1e-9 || truncated {
+        // Already on a step apart from floating-point noise or truncation
+        nearest
+    } else {
+        match mode {
+            RoundingMode::Nearest => nearest,
+            RoundingMode::TowardPlus => steps.ceil(),
+            RoundingMode::TowardMinus => steps.floor(),
+        }
+    };
+    // Adding zero turns −0.0 into 0.0
+    let rounded = steps * size + 0.0;
+    match step {
+        PowerStep::Step012 => (rounded * 100.0).trunc() / 100.0,
+        _ => rounded,
+    }
+}
+
+/// Normalize an axis into the clinical range (0, 180] (degrees).
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// assert_eq!(normalize_axis_deg(-5.0), 175.0);
+/// assert_eq!(normalize_axis_deg(0.0), 180.0);
+/// assert_eq!(normalize_axis_deg(185.0), 5.0);
+/// ```
+pub fn normalize_axis_deg(axis_deg: f64) -> f64 {
+    let axis = axis_deg.rem_euclid(180.0);
+    if axis == 0.0 { 180.0 } else { axis }
+}
+
+impl SpheroCyl {
+    /// Returns the spherical equivalent, `S + C/2` (D).
+    pub fn spherical_equivalent(&self) -> f64 {
+        self.sphere + self.cylinder / 2.0
+    }
+
+    /// Rounds the prescription to clinical steps.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    ///
+    /// let rx = SpheroCyl { sphere: -2.3712, cylinder: -0.8831, axis_deg: 37.4 };
+    /// let rounded = rx.rounded(RoundingOptions::default());
+    /// assert_eq!(rounded.rx.sphere, -2.25);
+    /// assert_eq!(rounded.rx.cylinder, -1.0);
+    /// assert_eq!(rounded.rx.axis_deg, 37.0);
+    /// assert!(rounded.error.magnitude() < 0.125);
+    /// ```
+    pub fn rounded(&self, options: RoundingOptions) -> RoundedRx {
+        let cylinder = round_power(self.cylinder, options.power_step, options.mode);
+        let sphere = if options.preserve_spherical_equivalent {
+            let target = self.spherical_equivalent() - cylinder / 2.0;
+            round_power(target, options.power_step, options.mode)
+        } else {
+            round_power(self.sphere, options.power_step, options.mode)
+        };
+        let axis_size = match options.axis_step {
+            AxisStep::Deg1 => 1.0,
+            AxisStep::Deg5 => 5.0,
+        };
+        let axis_deg = normalize_axis_deg((self.axis_deg / axis_size).round() * axis_size);
+
+        let rx = SpheroCyl {
+            sphere,
+            cylinder,
+            axis_deg,
+        };
+        RoundedRx {
+            rx,
+            error: PowerVector::from(rx) - PowerVector::from(*self),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-12;
+
+    #[test]
+    fn rounding_modes_and_steps() {
+        let r = round_power;
+        assert_eq!(
+            r(-0.88, PowerStep::Step025, RoundingMode::TowardPlus),
+            -0.75
+        );
+        assert_eq!(r(-0.88, PowerStep::Step0125, RoundingMode::Nearest), -0.875);
+        assert_eq!(r(-0.88, PowerStep::Step012, RoundingMode::Nearest), -0.87);
+        assert_eq!(r(1.60, PowerStep::Step012, RoundingMode::TowardPlus), 1.62);
+        assert_eq!(r(0.125, PowerStep::Step025, RoundingMode::Nearest), 0.25);
+        assert_eq!(r(2.0, PowerStep::Step025, RoundingMode::TowardMinus), 2.0);
+        assert_eq!(
+            r(2.25 + 1e-12, PowerStep::Step025, RoundingMode::TowardPlus),
+            2.25
+        );
+        assert!(r(-0.1, PowerStep::Step025, RoundingMode::Nearest).is_sign_positive());
+    }
+
+    #[test]
+    fn two_decimal_steps_are_kept_in_every_mode() {
+        for mode in [
+            RoundingMode::Nearest,
+            RoundingMode::TowardPlus,
+            RoundingMode::TowardMinus,
+        ] {
+            for power in [0.37, -0.87, 1.62, -2.12, 0.25] {
+                assert_eq!(round_power(power, PowerStep::Step012, mode), power);
+            }
+        }
+        // Just outside the tolerance the directional modes still move
+        assert_eq!(
+            round_power(0.369, PowerStep::Step012, RoundingMode::TowardMinus),
+            0.25
+        );
+        assert_eq!(
+            round_power(0.369, PowerStep::Step012, RoundingMode::TowardPlus),
+            0.37
+        );
+        // Powers past a step are not pulled back onto it
+        assert_eq!(
+            round_power(0.254, PowerStep::Step012, RoundingMode::TowardPlus),
+            0.37
+        );
+        assert_eq!(
+            round_power(0.254, PowerStep::Step0125, RoundingMode::TowardPlus),
+            0.375
+        );
+        assert_eq!(
+            round_power(0.3799, PowerStep::Step012, RoundingMode::TowardPlus),
+            0.5
+        );
+        assert_eq!(
+            round_power(-2.254, PowerStep::Step012, RoundingMode::TowardMinus),
+            -2.37
+        );
+    }
+
+    #[test]
+    fn preserving_spherical_equivalent() {
+        // SE −1.93: rounding independently gives −1.50 −0.50 (SE −1.75)
+        let rx = SpheroCyl {
+            sphere: -1.62,
+            cylinder: -0.62,
+            axis_deg: 90.0,
+        };
+        let plain = rx.rounded(RoundingOptions::default());
+        assert_eq!(plain.rx.sphere, -1.5);
+        let preserved = rx.rounded(RoundingOptions {
+            preserve_spherical_equivalent: true,
+            ..RoundingOptions::default()
+        });
+        assert_eq!(preserved.rx.cylinder, -0.5);
+        assert_eq!(preserved.rx.sphere, -1.75);
+        assert!(preserved.error.m.abs() < plain.error.m.abs());
+    }
+
+    #[test]
+    fn axis_rounds_into_clinical_range() {
+        let rx = SpheroCyl {
+            sphere: 0.0,
+            cylinder: -1.0,
+            axis_deg: 178.0,
+        };
+        let five = rx.rounded(RoundingOptions {
+            axis_step: AxisStep::Deg5,
+            ..RoundingOptions::default()
+        });
+        assert_eq!(five.rx.axis_deg, 180.0);
+
+        let negative = SpheroCyl {
+            axis_deg: -5.2,
+            ..rx
+        };
+        assert_eq!(
+            negative.rounded(RoundingOptions::default()).rx.axis_deg,
+            175.0
+        );
+        assert_eq!(
+            SpheroCyl {
+                axis_deg: 0.3,
+                ..rx
+            }
+            .rounded(RoundingOptions::default())
+            .rx
+            .axis_deg,
+            180.0
+        );
+    }
+
+    #[test]
+    fn error_is_zero_for_exact_steps() {
+        let rx = SpheroCyl {
+            sphere: -2.5,
+            cylinder: -1.25,
+            axis_deg: 180.0,
+        };
+        let rounded = rx.rounded(RoundingOptions::default());
+        assert_abs_diff_eq!(rounded.error.magnitude(), 0.0, epsilon = EPS);
+    }
+}
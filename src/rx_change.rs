@@ -0,0 +1,298 @@
+//! Prescription change between two examinations.
+//!
+//! ## Overview
+//! [`compare_rx`] describes how a lens prescription changed from one visit
+//! to the next, and [`compare_spectacle_rx`] does the same for both eyes and
+//! the anisometropia between them. Changes beyond [`RxChangeThresholds`] are
+//! flagged as likely to need adaptation.
+//!
+//! ## Formulas
+//! ```text
+//! Difference lens:   D = new ⊕ (−old)            (obliquely crossed cylinders)
+//! ΔSE:               SE_new − SE_old
+//! Vector distance:   |PV_new − PV_old|           (power vectors, D)
+//! Axis rotation:     α_new − α_old, wrapped to (−90, 90]
+//! Anisometropia:     |SE_OD − SE_OS|
+//! ```
+//!
+//! ## Conventions
+//! - Cylinder and axis changes are taken in minus-cylinder form.
+//! - Axis rotation is positive counterclockwise and is 0 when either Rx has
+//!   no cylinder.
+//! - The axis flag applies when the new cylinder exceeds
+//!   `axis_min_cylinder` in magnitude.
+
+use crate::*;
+
+/// Changes beyond which a new prescription usually needs adaptation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RxChangeThresholds {
+    /// Largest change in spherical equivalent (D).
+    ///
+    /// Example: flag SE shifts over 0.50 D → `spherical_equivalent = 0.5`
+    pub spherical_equivalent: f64,
+
+    /// Largest change in cylinder magnitude (D).
+    ///
+    /// Example: flag cylinder changes over 0.50 D → `cylinder = 0.5`
+    pub cylinder: f64,
+
+    /// Largest axis rotation (degrees).
+    ///
+    /// Example: flag axis rotations over 10° → `axis_deg = 10.0`
+    pub axis_deg: f64,
+
+    /// Cylinder magnitude above which the axis rotation is checked (D).
+    ///
+    /// Example: check the axis only above 1.00 DC → `axis_min_cylinder = 1.0`
+    pub axis_min_cylinder: f64,
+
+    /// Largest change in anisometropia (D).
+    ///
+    /// Example: flag anisometropia changes over 1.00 D → `anisometropia = 1.0`
+    pub anisometropia: f64,
+}
+
+impl Default for RxChangeThresholds {
+    /// 0.50 D of spherical equivalent or cylinder, 10° of axis for cylinders
+    /// over 1.00 D, and 1.00 D of anisometropia.
+    fn default() -> Self {
+        Self {
+            spherical_equivalent: 0.5,
+            cylinder: 0.5,
+            axis_deg: 10.0,
+            axis_min_cylinder: 1.0,
+            anisometropia: 1.0,
+        }
+    }
+}
+
+/// Change of one eye's prescription between two visits.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RxChange {
+    /// Lens that added to the old Rx gives the new one.
+    pub difference: SpheroCyl,
+
+    /// Change in spherical equivalent (D), positive toward plus.
+    pub spherical_equivalent_change: f64,
+
+    /// Change in cylinder magnitude (D), positive when the cylinder grew.
+    pub cylinder_change: f64,
+
+    /// Distance between the old and new power vectors (D).
+    pub vector_distance: f64,
+
+    /// Rotation of the cylinder axis (degrees), in (−90, 90].
+    pub axis_rotation_deg: f64,
+
+    /// Spherical equivalent changed more than the threshold.
+    pub spherical_equivalent_flag: bool,
+
+    /// Cylinder magnitude changed more than the threshold.
+    pub cylinder_flag: bool,
+
+    /// Axis rotated more than the threshold on a significant cylinder.
+    pub axis_flag: bool,
+}
+
+impl RxChange {
+    /// Returns `true` if any change exceeds its threshold.
+    pub fn needs_adaptation(&self) -> bool {
+        self.spherical_equivalent_flag || self.cylinder_flag || self.axis_flag
+    }
+}
+
+/// Change of a spectacle prescription between two visits.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SpectacleRxChange {
+    /// Right eye.
+    pub od: RxChange,
+
+    /// Left eye.
+    pub os: RxChange,
+
+    /// Anisometropia of the old Rx (D).
+    pub old_anisometropia: f64,
+
+    /// Anisometropia of the new Rx (D).
+    pub new_anisometropia: f64,
+
+    /// Anisometropia changed more than the threshold.
+    pub anisometropia_flag: bool,
+}
+
+impl SpectacleRxChange {
+    /// Returns the change in anisometropia (D), positive when it grew.
+    pub fn anisometropia_change(&self) -> f64 {
+        self.new_anisometropia - self.old_anisometropia
+    }
+
+    /// Returns `true` if either eye or the anisometropia exceeds a threshold.
+    pub fn needs_adaptation(&self) -> bool {
+        self.od.needs_adaptation() || self.os.needs_adaptation() || self.anisometropia_flag
+    }
+}
+
+/// Compare two prescriptions for the same eye.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let old = SpheroCyl { sphere: -2.00, cylinder: -1.50, axis_deg: 175.0 };
+/// let new = SpheroCyl { sphere: -2.25, cylinder: -1.50, axis_deg: 10.0 };
+/// let change = compare_rx(old, new, RxChangeThresholds::default());
+///
+/// assert_abs_diff_eq!(change.spherical_equivalent_change, -0.25, epsilon = 1e-12);
+/// assert_abs_diff_eq!(change.axis_rotation_deg, 15.0, epsilon = 1e-9);
+/// assert!(change.axis_flag);
+/// assert!(change.needs_adaptation());
+/// ```
+pub fn compare_rx(old: SpheroCyl, new: SpheroCyl, thresholds: RxChangeThresholds) -> RxChange {
+    let negated_old = SpheroCyl {
+        sphere: -old.sphere,
+        cylinder: -old.cylinder,
+        axis_deg: old.axis_deg,
+    };
+    let difference = crossed_cylinders(new, negated_old);
+
+    let old_minus = old.normalized(CylinderForm::Minus);
+    let new_minus = new.normalized(CylinderForm::Minus);
+    let spherical_equivalent_change = new.spherical_equivalent() - old.spherical_equivalent();
+    let cylinder_change = new_minus.cylinder.abs() - old_minus.cylinder.abs();
+    let axis_rotation_deg = if old_minus.cylinder == 0.0 || new_minus.cylinder == 0.0 {
+        0.0
+    } else {
+        let rotation = (new_minus.axis_deg - old_minus.axis_deg).rem_euclid(180.0);
+        if rotation > 90.0 {
+            rotation - 180.0
+        } else {
+            rotation
+        }
+    };
+
+    RxChange {
+        difference,
+        spherical_equivalent_change,
+        cylinder_change,
+        vector_distance: PowerVector::from(new).distance(&PowerVector::from(old)),
+        axis_rotation_deg,
+        spherical_equivalent_flag: spherical_equivalent_change.abs()
+            > thresholds.spherical_equivalent,
+        cylinder_flag: cylinder_change.abs() > thresholds.cylinder,
+        axis_flag: new_minus.cylinder.abs() > thresholds.axis_min_cylinder
+            && axis_rotation_deg.abs() > thresholds.axis_deg,
+    }
+}
+
+/// Compare two spectacle prescriptions, eye by eye and for anisometropia.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let lens = |sphere| EyeRx::from_lens(SpheroCyl { sphere, cylinder: 0.0, axis_deg: 0.0 });
+/// let old = SpectacleRx { od: lens(-1.0), os: lens(-1.25) };
+/// let new = SpectacleRx { od: lens(-1.0), os: lens(-2.50) };
+///
+/// let change = compare_spectacle_rx(&old, &new, RxChangeThresholds::default());
+/// assert_eq!(change.anisometropia_change(), 1.25);
+/// assert!(change.anisometropia_flag);
+/// assert!(!change.od.needs_adaptation());
+/// ```
+pub fn compare_spectacle_rx(
+    old: &SpectacleRx,
+    new: &SpectacleRx,
+    thresholds: RxChangeThresholds,
+) -> SpectacleRxChange {
+    let anisometropia = |rx: &SpectacleRx| {
+        (rx.od.lens.spherical_equivalent() - rx.os.lens.spherical_equivalent()).abs()
+    };
+    let old_anisometropia = anisometropia(old);
+    let new_anisometropia = anisometropia(new);
+
+    SpectacleRxChange {
+        od: compare_rx(old.od.lens, new.od.lens, thresholds),
+        os: compare_rx(old.os.lens, new.os.lens, thresholds),
+        old_anisometropia,
+        new_anisometropia,
+        anisometropia_flag: (new_anisometropia - old_anisometropia).abs()
+            > thresholds.anisometropia,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    #[test]
+    fn difference_lens_added_to_old_gives_new() {
+        let old = SpheroCyl {
+            sphere: 1.25,
+            cylinder: -0.75,
+            axis_deg: 30.0,
+        };
+        let new = SpheroCyl {
+            sphere: 0.75,
+            cylinder: 1.0,
+            axis_deg: 100.0,
+        };
+        let change = compare_rx(old, new, RxChangeThresholds::default());
+        let back = crossed_cylinders(old, change.difference);
+        assert!(back.approx_eq(&new, 1e-9));
+        assert_abs_diff_eq!(
+            change.vector_distance,
+            PowerVector::from(change.difference).magnitude(),
+            epsilon = 1e-9
+        );
+    }
+
+    #[test]
+    fn axis_rotation_wraps_and_ignores_spheres() {
+        let cyl = |axis_deg| SpheroCyl {
+            sphere: 0.0,
+            cylinder: -0.75,
+            axis_deg,
+        };
+        let change = compare_rx(cyl(10.0), cyl(170.0), RxChangeThresholds::default());
+        assert_abs_diff_eq!(change.axis_rotation_deg, -20.0, epsilon = 1e-9);
+        // A 0.75 D cylinder is below the 1.00 D axis threshold
+        assert!(!change.axis_flag);
+
+        let sphere = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 45.0,
+        };
+        let change = compare_rx(sphere, cyl(90.0), RxChangeThresholds::default());
+        assert_eq!(change.axis_rotation_deg, 0.0);
+        assert_abs_diff_eq!(change.cylinder_change, 0.75, epsilon = 1e-12);
+        assert!(change.cylinder_flag);
+    }
+
+    #[test]
+    fn notation_change_is_not_a_change() {
+        let old = SpheroCyl {
+            sphere: -1.0,
+            cylinder: -1.5,
+            axis_deg: 180.0,
+        };
+        let change = compare_rx(old, old.transpose(), RxChangeThresholds::default());
+        assert_abs_diff_eq!(change.vector_distance, 0.0, epsilon = 1e-12);
+        assert_abs_diff_eq!(change.axis_rotation_deg, 0.0, epsilon = 1e-9);
+        assert!(!change.needs_adaptation());
+    }
+}
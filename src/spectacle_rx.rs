@@ -0,0 +1,104 @@
+//! Binocular spectacle prescriptions.
+//!
+//! ## Overview
+//! [`SpectacleRx`] groups the right and left [`EyeRx`] of a spectacle order:
+//! lens power, near add, prescribed prism and centration.
+//!
+//! ## Conventions
+//! - Monocular PDs are measured from the bridge center to each pupil (mm).
+//! - Optical center heights are measured from the lowest point of the box (mm).
+//! - A prism of 0Δ on both components means no prescribed prism.
+
+use crate::*;
+
+/// Prescription and centration for one eye.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct EyeRx {
+    /// Distance lens power.
+    pub lens: SpheroCyl,
+
+    /// Near addition (D), if any.
+    pub add: Option<f64>,
+
+    /// Prescribed prism.
+    pub prism: CombinedPrism,
+
+    /// Monocular distance PD (mm), if known.
+    pub monocular_pd_mm: Option<f64>,
+
+    /// Optical center height above the lowest point of the box (mm), if known.
+    pub oc_height_mm: Option<f64>,
+}
+
+impl EyeRx {
+    /// Creates a prescription with only a lens power.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let eye = EyeRx::from_lens(SpheroCyl { sphere: -1.0, cylinder: 0.0, axis_deg: 0.0 });
+    /// assert!(eye.add.is_none());
+    /// assert_eq!(eye.prism.magnitude(), 0.0);
+    /// ```
+    pub fn from_lens(lens: SpheroCyl) -> Self {
+        Self {
+            lens,
+            add: None,
+            prism: CombinedPrism {
+                horizontal: HorizontalPrism::from_signed(0.0),
+                vertical: VerticalPrism::from_signed(0.0),
+            },
+            monocular_pd_mm: None,
+            oc_height_mm: None,
+        }
+    }
+
+    /// Returns the near Rx, or the distance lens if there is no add.
+    pub fn near_lens(&self) -> SpheroCyl {
+        near_rx(self.lens, self.add.unwrap_or(0.0))
+    }
+}
+
+/// A binocular spectacle prescription.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SpectacleRx {
+    /// Right eye.
+    pub od: EyeRx,
+
+    /// Left eye.
+    pub os: EyeRx,
+}
+
+impl SpectacleRx {
+    /// Returns the prescription for one eye.
+    pub fn eye(&self, eye: Eye) -> &EyeRx {
+        match eye {
+            Eye::OD => &self.od,
+            Eye::OS => &self.os,
+        }
+    }
+
+    /// Returns the binocular distance PD, if both monocular PDs are known.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let lens = SpheroCyl { sphere: -1.0, cylinder: 0.0, axis_deg: 0.0 };
+    /// let rx = SpectacleRx {
+    ///     od: EyeRx { monocular_pd_mm: Some(32.0), ..EyeRx::from_lens(lens) },
+    ///     os: EyeRx { monocular_pd_mm: Some(31.5), ..EyeRx::from_lens(lens) },
+    /// };
+    /// assert_eq!(rx.binocular_pd_mm(), Some(63.5));
+    /// ```
+    pub fn binocular_pd_mm(&self) -> Option<f64> {
+        Some(self.od.monocular_pd_mm? + self.os.monocular_pd_mm?)
+    }
+}
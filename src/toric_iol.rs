@@ -0,0 +1,393 @@
+//! Toric IOL cylinder and axis planning.
+//!
+//! ## Overview
+//! - Predicts the postoperative corneal astigmatism by combining the preoperative
+//!   keratometry with the surgically induced astigmatism (SIA) of the incision.
+//! - Selects the toric IOL whose corneal-plane cylinder leaves the least residual
+//!   astigmatism when aligned with the predicted steep meridian.
+//! - Tabulates the residual astigmatism for every 5° of IOL misalignment.
+//!
+//! ## Method
+//! All powers are combined as power matrices via [`crossed_cylinders`]:
+//! ```text
+//! Postop cornea = K ⊕ SIA
+//! Residual      = Postop cornea ⊕ IOL(corneal plane, axis + misalignment)
+//! ```
+//!
+//! ## Conventions
+//! - Powers are in **diopters (D)**, axes in **degrees [0,180)**.
+//! - The SIA flattens the incision meridian and steepens the orthogonal meridian
+//!   by the same amount, leaving the mean corneal power unchanged.
+//! - The implantation axis is the IOL's flat (plus-cylinder) axis mark, placed on
+//!   the predicted steep corneal meridian.
+
+use crate::*;
+
+/// Typical ratio between IOL-plane and corneal-plane cylinder for an average eye.
+pub const IOL_TO_CORNEAL_CYLINDER_RATIO: f64 = 1.46;
+
+/// Step between rows of the rotation-error sensitivity table, in degrees.
+pub const ROTATION_SENSITIVITY_STEP_DEG: f64 = 5.0;
+
+/// Largest misalignment reported in the rotation-error sensitivity table, in degrees.
+pub const ROTATION_SENSITIVITY_MAX_DEG: f64 = 90.0;
+
+/// Surgically induced astigmatism of a corneal incision.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct SurgicallyInducedAstigmatism {
+    /// Magnitude of the SIA (D).
+    /// Example: a 2.4 mm temporal clear-corneal incision → `magnitude = 0.3`.
+    pub magnitude: f64,
+
+    /// Meridian of the incision in degrees, range [0, 180).
+    pub incision_axis_deg: f64,
+}
+
+impl SurgicallyInducedAstigmatism {
+    /// Returns the corneal power change caused by the incision.
+    ///
+    /// The incision meridian flattens by `magnitude / 2` and the orthogonal
+    /// meridian steepens by `magnitude / 2`.
+    pub fn to_sphero_cyl(&self) -> SpheroCyl {
+        SpheroCyl {
+            sphere: self.magnitude / 2.0,
+            cylinder: -self.magnitude,
+            axis_deg: (self.incision_axis_deg + 90.0).rem_euclid(180.0),
+        }
+    }
+}
+
+/// A toric IOL available for implantation.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ToricIolModel {
+    /// Model name as listed by the manufacturer, e.g. `"T3"`.
+    pub name: String,
+
+    /// Cylinder power at the IOL plane (D).
+    pub iol_cylinder: f64,
+
+    /// Equivalent cylinder power at the corneal plane (D).
+    pub corneal_cylinder: f64,
+}
+
+impl ToricIolModel {
+    /// Creates a model from its IOL-plane cylinder, estimating the corneal-plane
+    /// cylinder with [`IOL_TO_CORNEAL_CYLINDER_RATIO`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// let model = ToricIolModel::from_iol_cylinder("T3", 1.50);
+    /// assert!((model.corneal_cylinder - 1.027).abs() < 1e-3);
+    /// ```
+    pub fn from_iol_cylinder(name: impl Into<String>, iol_cylinder: f64) -> Self {
+        Self {
+            name: name.into(),
+            iol_cylinder,
+            corneal_cylinder: iol_cylinder / IOL_TO_CORNEAL_CYLINDER_RATIO,
+        }
+    }
+}
+
+/// Residual astigmatism for a given IOL misalignment.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct RotationError {
+    /// Counter-clockwise rotation of the IOL away from the implantation axis (degrees).
+    ///
+    /// Clockwise rotation by the same amount gives the same residual magnitude,
+    /// mirrored about the implantation axis.
+    pub misalignment_deg: f64,
+
+    /// Predicted residual corneal-plane astigmatism.
+    pub residual: Astigmatism,
+}
+
+/// Result of toric IOL planning.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ToricIolPlan {
+    /// Recommended model from the supplied table.
+    pub model: ToricIolModel,
+
+    /// Axis on which to align the IOL's axis marks, in degrees [0, 180).
+    pub implantation_axis_deg: f64,
+
+    /// Predicted postoperative corneal astigmatism, including the SIA.
+    pub expected_corneal_astigmatism: Astigmatism,
+
+    /// Predicted residual astigmatism with the IOL perfectly aligned.
+    pub predicted_residual: Astigmatism,
+
+    /// Residual astigmatism for every [`ROTATION_SENSITIVITY_STEP_DEG`] of misalignment,
+    /// from 0° up to [`ROTATION_SENSITIVITY_MAX_DEG`].
+    pub rotation_sensitivity: Vec<RotationError>,
+}
+
+/// Predict the postoperative corneal astigmatism from keratometry and SIA.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// // 1.00 D with-the-rule, temporal incision flattens 180 by 0.50 D
+/// let k = Keratometry { flat_k: 43.0, steep_k: 44.0, steep_axis_deg: 90.0 };
+/// let sia = SurgicallyInducedAstigmatism { magnitude: 0.5, incision_axis_deg: 180.0 };
+/// let postop = expected_corneal_astigmatism(k, sia);
+/// assert_abs_diff_eq!(postop.magnitude, 1.5, epsilon = 1e-9);
+/// assert_abs_diff_eq!(postop.axis_deg, 90.0, epsilon = 1e-9);
+/// ```
+pub fn expected_corneal_astigmatism(
+    k: Keratometry,
+    sia: SurgicallyInducedAstigmatism,
+) -> Astigmatism {
+    let postop = crossed_cylinders(k.to_sphero_cyl(), sia.to_sphero_cyl());
+    Astigmatism::new(-postop.cylinder, postop.axis_deg)
+}
+
+/// Predict the residual astigmatism left by a toric IOL.
+///
+/// `corneal_cylinder` is the IOL's cylinder at the corneal plane and
+/// `iol_axis_deg` the meridian its axis marks are aligned with.
+pub fn toric_iol_residual(
+    cornea: Astigmatism,
+    corneal_cylinder: f64,
+    iol_axis_deg: f64,
+) -> Astigmatism {
+    let correction = SpheroCyl {
+        sphere: 0.0,
+        cylinder: corneal_cylinder,
+        axis_deg: iol_axis_deg,
+    };
+    let residual = crossed_cylinders(cornea.to_sphero_cyl(), correction);
+    Astigmatism::new(-residual.cylinder, residual.axis_deg)
+}
+
+/// Plan a toric IOL from preoperative keratometry, SIA and a table of available models.
+///
+/// The implantation axis is the predicted postoperative steep meridian. The model
+/// leaving the smallest residual astigmatism is recommended; on a tie the model
+/// with the lower cylinder is preferred to avoid flipping the astigmatism axis.
+///
+/// Returns `None` if `models` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let k = Keratometry { flat_k: 42.5, steep_k: 44.25, steep_axis_deg: 85.0 };
+/// let sia = SurgicallyInducedAstigmatism { magnitude: 0.3, incision_axis_deg: 180.0 };
+/// let models = vec![
+///     ToricIolModel::from_iol_cylinder("T2", 1.00),
+///     ToricIolModel::from_iol_cylinder("T3", 1.50),
+///     ToricIolModel::from_iol_cylinder("T4", 2.25),
+///     ToricIolModel::from_iol_cylinder("T5", 3.00),
+/// ];
+///
+/// let plan = plan_toric_iol(k, sia, &models).unwrap();
+/// assert_eq!(plan.model.name, "T5");
+/// assert!(plan.predicted_residual.magnitude < 0.2);
+/// ```
+pub fn plan_toric_iol(
+    k: Keratometry,
+    sia: SurgicallyInducedAstigmatism,
+    models: &[ToricIolModel],
+) -> Option<ToricIolPlan> {
+    let cornea = expected_corneal_astigmatism(k, sia);
+    let implantation_axis_deg = cornea.axis_deg;
+
+    let mut best: Option<(&ToricIolModel, Astigmatism)> = None;
+    for model in models {
+        let residual = toric_iol_residual(cornea, model.corneal_cylinder, implantation_axis_deg);
+        let better = match best {
+            None => true,
+            Some((best_model, best_residual)) => {
+                let diff = residual.magnitude - best_residual.magnitude;
+                diff < -1e-9
+                    || (diff.abs() <= 1e-9 && model.corneal_cylinder < best_model.corneal_cylinder)
+            }
+        };
+        if better {
+            best = Some((model, residual));
+        }
+    }
+    let (model, predicted_residual) = best?;
+
+    let steps = (ROTATION_SENSITIVITY_MAX_DEG / ROTATION_SENSITIVITY_STEP_DEG).round() as usize;
+    let rotation_sensitivity = (0..=steps)
+        .map(|i| {
+            let misalignment_deg = i as f64 * ROTATION_SENSITIVITY_STEP_DEG;
+            RotationError {
+                misalignment_deg,
+                residual: toric_iol_residual(
+                    cornea,
+                    model.corneal_cylinder,
+                    implantation_axis_deg + misalignment_deg,
+                ),
+            }
+        })
+        .collect();
+
+    Some(ToricIolPlan {
+        model: model.clone(),
+        implantation_axis_deg,
+        expected_corneal_astigmatism: cornea,
+        predicted_residual,
+        rotation_sensitivity,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    fn models() -> Vec<ToricIolModel> {
+        vec![
+            ToricIolModel {
+                name: "A".into(),
+                iol_cylinder: 1.5,
+                corneal_cylinder: 1.0,
+            },
+            ToricIolModel {
+                name: "B".into(),
+                iol_cylinder: 2.25,
+                corneal_cylinder: 1.5,
+            },
+            ToricIolModel {
+                name: "C".into(),
+                iol_cylinder: 3.0,
+                corneal_cylinder: 2.0,
+            },
+        ]
+    }
+
+    #[test]
+    fn sia_against_the_steep_meridian_reduces_astigmatism() {
+        // Incision on the steep meridian flattens it
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 44.0,
+            steep_axis_deg: 90.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.4,
+            incision_axis_deg: 90.0,
+        };
+        let postop = expected_corneal_astigmatism(k, sia);
+        assert_abs_diff_eq!(postop.magnitude, 0.6, epsilon = EPS);
+        assert_abs_diff_eq!(postop.axis_deg, 90.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn oblique_sia_rotates_steep_meridian() {
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 44.0,
+            steep_axis_deg: 90.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.5,
+            incision_axis_deg: 135.0,
+        };
+        // Double-angle sum: (−1, 0) + (0, 0.5) → 1.118 D @ 76.72°
+        let postop = expected_corneal_astigmatism(k, sia);
+        assert_abs_diff_eq!(postop.magnitude, 1.25f64.sqrt(), epsilon = EPS);
+        let expected_axis = 0.5f64.atan2(-1.0).to_degrees() / 2.0;
+        assert_abs_diff_eq!(postop.axis_deg, expected_axis, epsilon = EPS);
+    }
+
+    #[test]
+    fn selects_model_closest_to_corneal_astigmatism() {
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 44.5,
+            steep_axis_deg: 10.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.0,
+            incision_axis_deg: 180.0,
+        };
+        let plan = plan_toric_iol(k, sia, &models()).unwrap();
+        assert_eq!(plan.model.name, "B");
+        assert_abs_diff_eq!(plan.implantation_axis_deg, 10.0, epsilon = EPS);
+        assert_abs_diff_eq!(plan.predicted_residual.magnitude, 0.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn tie_prefers_lower_cylinder() {
+        // 1.25 D lies halfway between 1.00 and 1.50
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 44.25,
+            steep_axis_deg: 90.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.0,
+            incision_axis_deg: 180.0,
+        };
+        let plan = plan_toric_iol(k, sia, &models()).unwrap();
+        assert_eq!(plan.model.name, "A");
+        assert_abs_diff_eq!(plan.predicted_residual.magnitude, 0.25, epsilon = EPS);
+        // Undercorrection keeps the residual on the original steep meridian
+        assert_abs_diff_eq!(plan.predicted_residual.axis_deg, 90.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn rotation_sensitivity_follows_two_sine_rule() {
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 45.0,
+            steep_axis_deg: 90.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.0,
+            incision_axis_deg: 180.0,
+        };
+        let plan = plan_toric_iol(k, sia, &models()).unwrap();
+        assert_eq!(plan.model.name, "C");
+        assert_eq!(plan.rotation_sensitivity.len(), 19);
+
+        // Full correction misaligned by θ leaves 2·C·sin(θ)
+        for row in &plan.rotation_sensitivity {
+            let expected = 2.0 * 2.0 * row.misalignment_deg.to_radians().sin();
+            assert_abs_diff_eq!(row.residual.magnitude, expected, epsilon = 1e-9);
+        }
+        // At 30° the residual equals the original astigmatism
+        assert_abs_diff_eq!(
+            plan.rotation_sensitivity[6].residual.magnitude,
+            2.0,
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn empty_model_table_returns_none() {
+        let k = Keratometry {
+            flat_k: 43.0,
+            steep_k: 44.0,
+            steep_axis_deg: 90.0,
+        };
+        let sia = SurgicallyInducedAstigmatism {
+            magnitude: 0.3,
+            incision_axis_deg: 180.0,
+        };
+        assert!(plan_toric_iol(k, sia, &[]).is_none());
+    }
+}
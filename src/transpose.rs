@@ -88,11 +88,11 @@ impl SpheroCyl {
 ///     axis_deg: 90.0,
 /// };
 /// 
-/// // Transpose to minus cylinder form: -0.50 DS / +1.50 DC × 180
+/// // Transpose to minus cylinder form: -0.50 DS / +1.50 DC × 0
 /// let minus_form = transpose(plus_form);
 /// assert_eq!(minus_form.sphere, -0.50);
 /// assert_eq!(minus_form.cylinder, 1.50);
-/// assert_eq!(minus_form.axis_deg, 180.0);
+/// assert_eq!(minus_form.axis_deg, 0.0);
 /// ```
 pub fn transpose(lens: SpheroCyl) -> SpheroCyl {
     lens.transpose()
@@ -0,0 +1,888 @@
+//! Tolerance verification of finished spectacle lenses.
+//!
+//! ## Overview
+//! Compares a lensmeter reading with the ordered prescription using the
+//! ANSI Z80.1 or ISO 21987 tolerance tables, and reports each item with the
+//! tolerance that was applied.
+//!
+//! - [`verify_lens`]: sphere, cylinder and axis of a single lens.
+//! - [`verify_spectacles`]: both lenses plus add, prism imbalance and PD.
+//!
+//! If the lensmeter is calibrated for a different index than the lens
+//! material, convert the reading first with [`convert_rx`], or compare
+//! against [`simulate_lensmeter_reading`] of the ordered Rx.
+//!
+//! ## Tolerances
+//! ```text
+//! ANSI Z80.1
+//!   Sphere     |F| ≤ 6.50: ±0.13 D            |F| > 6.50: ±2%
+//!   Cylinder   C ≤ 2.00: ±0.13 D   ≤ 4.50: ±0.15 D   > 4.50: ±4%
+//!   Axis       C ≤ 0.25: ±14°  ≤ 0.50: ±7°  ≤ 0.75: ±5°  ≤ 1.50: ±3°  > 1.50: ±2°
+//!   Imbalance  horizontal 0.67Δ or 2.5 mm,  vertical 0.33Δ or 1.0 mm
+//!
+//! ISO 21987
+//!   Sphere     |F| ≤ 9.00: ±0.12 D  ≤ 12.00: ±0.18 D  ≤ 20.00: ±0.25 D  > 20.00: ±0.37 D
+//!   Cylinder   by |F| and C (see `cylinder_tolerance`)
+//!   Axis       C < 0.125: none  ≤ 0.25: ±16°  ≤ 0.50: ±9°  ≤ 0.75: ±6°  ≤ 1.50: ±4°  > 1.50: ±3°
+//!   Imbalance  horizontal 0.67Δ or 2.5 mm,  vertical 0.50Δ or 1.0 mm
+//!
+//! Both
+//!   Add        ≤ 4.00: ±0.12 D   > 4.00: ±0.18 D
+//!   PD         ±2.5 mm binocular
+//! ```
+//!
+//! ## Conventions
+//! - |F| is the absolute power of the strongest principal meridian and C the
+//!   absolute cylinder, both of the ordered lens.
+//! - A reading in the opposite cylinder form is transposed before comparing.
+//! - Imbalance tolerances are the larger of the prism limit and the prism
+//!   that the millimetre limit produces at the mean meridional power.
+//! - Horizontal imbalance adds BO in both eyes; vertical imbalance is the
+//!   difference of the signed vertical prisms (OD − OS).
+
+use crate::*;
+
+/// Standard that supplies the tolerance tables.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum ToleranceStandard {
+    /// ANSI Z80.1 (United States).
+    AnsiZ80_1,
+
+    /// ISO 21987 (international).
+    Iso21987,
+}
+
+impl ToleranceStandard {
+    /// Returns the sphere tolerance (± D) for the strongest meridian power.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// assert_abs_diff_eq!(ToleranceStandard::AnsiZ80_1.sphere_tolerance(-8.0), 0.16, epsilon = 1e-12);
+    /// assert_eq!(ToleranceStandard::Iso21987.sphere_tolerance(-8.0), 0.12);
+    /// ```
+    pub fn sphere_tolerance(&self, strongest_meridian: f64) -> f64 {
+        let f = strongest_meridian.abs();
+        match self {
+            ToleranceStandard::AnsiZ80_1 => {
+                if f <= 6.5 {
+                    0.13
+                } else {
+                    0.02 * f
+                }
+            }
+            ToleranceStandard::Iso21987 => match f {
+                f if f <= 9.0 => 0.12,
+                f if f <= 12.0 => 0.18,
+                f if f <= 20.0 => 0.25,
+                _ => 0.37,
+            },
+        }
+    }
+
+    /// Returns the cylinder tolerance (± D).
+    pub fn cylinder_tolerance(&self, cylinder: f64, strongest_meridian: f64) -> f64 {
+        let c = cylinder.abs();
+        match self {
+            ToleranceStandard::AnsiZ80_1 => match c {
+                c if c <= 2.0 => 0.13,
+                c if c <= 4.5 => 0.15,
+                _ => 0.04 * c,
+            },
+            ToleranceStandard::Iso21987 => {
+                // Rows by |F|, columns by C: ≤ 0.75, ≤ 4.00, ≤ 6.00, > 6.00
+                const TABLE: [[f64; 4]; 6] = [
+                    [0.09, 0.12, 0.18, 0.18],
+                    [0.12, 0.12, 0.18, 0.25],
+                    [0.12, 0.18, 0.18, 0.25],
+                    [0.12, 0.18, 0.25, 0.25],
+                    [0.18, 0.25, 0.25, 0.25],
+                    [0.25, 0.25, 0.37, 0.37],
+                ];
+                let row = [3.0, 6.0, 9.0, 12.0, 20.0]
+                    .iter()
+                    .position(|&limit| strongest_meridian.abs() <= limit)
+                    .unwrap_or(5);
+                let col = [0.75, 4.0, 6.0]
+                    .iter()
+                    .position(|&limit| c <= limit)
+                    .unwrap_or(3);
+                TABLE[row][col]
+            }
+        }
+    }
+
+    /// Returns the axis tolerance (± degrees), or `None` if the cylinder is too
+    /// weak for the axis to be checked.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// assert_eq!(ToleranceStandard::AnsiZ80_1.axis_tolerance_deg(-1.0), Some(3.0));
+    /// assert_eq!(ToleranceStandard::Iso21987.axis_tolerance_deg(-1.0), Some(4.0));
+    /// assert_eq!(ToleranceStandard::Iso21987.axis_tolerance_deg(0.0), None);
+    /// ```
+    pub fn axis_tolerance_deg(&self, cylinder: f64) -> Option<f64> {
+        let c = cylinder.abs();
+        let (minimum, table) = match self {
+            ToleranceStandard::AnsiZ80_1 => (0.0, [14.0, 7.0, 5.0, 3.0, 2.0]),
+            ToleranceStandard::Iso21987 => (0.125, [16.0, 9.0, 6.0, 4.0, 3.0]),
+        };
+        if c < minimum || c < 1e-9 {
+            return None;
+        }
+        let i = [0.25, 0.5, 0.75, 1.5]
+            .iter()
+            .position(|&limit| c <= limit)
+            .unwrap_or(4);
+        Some(table[i])
+    }
+
+    /// Returns the add tolerance (± D).
+    pub fn add_tolerance(&self, add: f64) -> f64 {
+        if add <= 4.0 { 0.12 } else { 0.18 }
+    }
+
+    /// Returns the horizontal prism imbalance tolerance (Δ) for a mean
+    /// horizontal meridian power.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// // Low power: 0.67Δ; at −6.00 D, 2.5 mm gives 1.5Δ
+    /// assert_eq!(ToleranceStandard::AnsiZ80_1.horizontal_imbalance_tolerance(-1.0), 0.67);
+    /// assert_abs_diff_eq!(ToleranceStandard::AnsiZ80_1.horizontal_imbalance_tolerance(-6.0), 1.5, epsilon = 1e-12);
+    /// ```
+    pub fn horizontal_imbalance_tolerance(&self, meridian_power: f64) -> f64 {
+        0.67_f64.max(0.25 * meridian_power.abs())
+    }
+
+    /// Returns the vertical prism imbalance tolerance (Δ) for a mean
+    /// vertical meridian power.
+    pub fn vertical_imbalance_tolerance(&self, meridian_power: f64) -> f64 {
+        let prism: f64 = match self {
+            ToleranceStandard::AnsiZ80_1 => 0.33,
+            ToleranceStandard::Iso21987 => 0.5,
+        };
+        prism.max(0.1 * meridian_power.abs())
+    }
+
+    /// Returns the binocular PD tolerance (± mm).
+    pub fn pd_tolerance_mm(&self) -> f64 {
+        2.5
+    }
+}
+
+/// Item checked by a verification.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub enum ToleranceItem {
+    /// Sphere power (D).
+    Sphere,
+
+    /// Cylinder power (D).
+    Cylinder,
+
+    /// Cylinder axis (degrees).
+    Axis,
+
+    /// Near addition (D).
+    Add,
+
+    /// Net horizontal prism imbalance (Δ).
+    HorizontalPrismImbalance,
+
+    /// Net vertical prism imbalance (Δ).
+    VerticalPrismImbalance,
+
+    /// Binocular distance PD (mm).
+    Pd,
+}
+
+/// Result of one tolerance check.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct ToleranceCheck {
+    /// Item checked.
+    pub item: ToleranceItem,
+
+    /// Eye, or `None` for a single lens or a binocular item.
+    pub eye: Option<Eye>,
+
+    /// Ordered value.
+    pub expected: f64,
+
+    /// Measured value.
+    pub measured: f64,
+
+    /// Measured minus expected (for axis, wrapped to ±90°).
+    pub deviation: f64,
+
+    /// Tolerance applied (±).
+    pub tolerance: f64,
+
+    /// `true` if `|deviation|` is within `tolerance`.
+    pub pass: bool,
+}
+
+impl ToleranceCheck {
+    fn new(
+        item: ToleranceItem,
+        eye: Option<Eye>,
+        expected: f64,
+        measured: f64,
+        deviation: f64,
+        tolerance: f64,
+    ) -> Self {
+        Self {
+            item,
+            eye,
+            expected,
+            measured,
+            deviation,
+            tolerance,
+            // Readings exactly at the limit pass despite rounding error
+            pass: deviation.abs() <= tolerance + 1e-9,
+        }
+    }
+}
+
+/// Per-item verification report.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct VerificationReport {
+    /// Standard used.
+    pub standard: ToleranceStandard,
+
+    /// Individual checks, in order OD, OS, then binocular items.
+    pub checks: Vec<ToleranceCheck>,
+}
+
+impl VerificationReport {
+    /// Returns `true` if every check passes.
+    pub fn passed(&self) -> bool {
+        self.checks.iter().all(|c| c.pass)
+    }
+
+    /// Returns the failed checks.
+    pub fn failures(&self) -> impl Iterator<Item = &ToleranceCheck> {
+        self.checks.iter().filter(|c| !c.pass)
+    }
+}
+
+/// Lensmeter reading of one finished lens.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct MeasuredLens {
+    /// Distance power read at the distance reference point.
+    pub lens: SpheroCyl,
+
+    /// Measured add (D), if read.
+    pub add: Option<f64>,
+
+    /// Prism read at the prism reference point.
+    pub prism: CombinedPrism,
+}
+
+/// Measurements of a finished pair of spectacles.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct MeasuredSpectacles {
+    /// Right lens.
+    pub od: MeasuredLens,
+
+    /// Left lens.
+    pub os: MeasuredLens,
+
+    /// Measured binocular distance between optical centers (mm), if read.
+    pub pd_mm: Option<f64>,
+}
+
+/// Returns the mean absolute power of two lenses in a meridian.
+fn mean_meridian_power(od: SpheroCyl, os: SpheroCyl, phi_deg: f64) -> f64 {
+    (od.power_at(phi_deg).abs() + os.power_at(phi_deg).abs()) / 2.0
+}
+
+/// Returns the absolute power of the strongest principal meridian.
+fn strongest_meridian(lens: SpheroCyl) -> f64 {
+    lens.sphere.abs().max((lens.sphere + lens.cylinder).abs())
+}
+
+fn lens_checks(
+    ordered: SpheroCyl,
+    measured: SpheroCyl,
+    eye: Option<Eye>,
+    standard: ToleranceStandard,
+) -> Vec<ToleranceCheck> {
+    // Compare in the cylinder form of the order
+    let measured = if ordered.cylinder * measured.cylinder < 0.0 {
+        transpose(measured)
+    } else {
+        measured
+    };
+    let strongest = strongest_meridian(ordered);
+
+    let mut checks = vec![
+        ToleranceCheck::new(
+            ToleranceItem::Sphere,
+            eye,
+            ordered.sphere,
+            measured.sphere,
+            measured.sphere - ordered.sphere,
+            standard.sphere_tolerance(strongest),
+        ),
+        ToleranceCheck::new(
+            ToleranceItem::Cylinder,
+            eye,
+            ordered.cylinder,
+            measured.cylinder,
+            measured.cylinder - ordered.cylinder,
+            standard.cylinder_tolerance(ordered.cylinder, strongest),
+        ),
+    ];
+    if let Some(tolerance) = standard.axis_tolerance_deg(ordered.cylinder) {
+        let deviation = (measured.axis_deg - ordered.axis_deg + 90.0).rem_euclid(180.0) - 90.0;
+        checks.push(ToleranceCheck::new(
+            ToleranceItem::Axis,
+            eye,
+            ordered.axis_deg,
+            measured.axis_deg,
+            deviation,
+            tolerance,
+        ));
+    }
+    checks
+}
+
+/// Verify a single lens reading against the ordered power.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let ordered = SpheroCyl { sphere: -2.00, cylinder: -1.00, axis_deg: 90.0 };
+/// let reading = SpheroCyl { sphere: -2.12, cylinder: -1.00, axis_deg: 94.0 };
+///
+/// let ansi = verify_lens(ordered, reading, ToleranceStandard::AnsiZ80_1);
+/// assert!(!ansi.passed()); // axis is 4° off, ANSI allows ±3°
+///
+/// let iso = verify_lens(ordered, reading, ToleranceStandard::Iso21987);
+/// assert!(iso.passed()); // ISO allows ±4°
+/// ```
+pub fn verify_lens(
+    ordered: SpheroCyl,
+    measured: SpheroCyl,
+    standard: ToleranceStandard,
+) -> VerificationReport {
+    VerificationReport {
+        standard,
+        checks: lens_checks(ordered, measured, None, standard),
+    }
+}
+
+/// Verify a finished pair of spectacles against the order.
+///
+/// The add is checked when both the order and the reading have one; the PD
+/// when the order has both monocular PDs and the PD was measured.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+///
+/// let lens = SpheroCyl { sphere: -3.00, cylinder: -0.50, axis_deg: 180.0 };
+/// let ordered = SpectacleRx {
+///     od: EyeRx { monocular_pd_mm: Some(32.0), ..EyeRx::from_lens(lens) },
+///     os: EyeRx { monocular_pd_mm: Some(32.0), ..EyeRx::from_lens(lens) },
+/// };
+/// let reading = MeasuredLens {
+///     lens,
+///     add: None,
+///     prism: CombinedPrism {
+///         horizontal: HorizontalPrism::from_signed(0.0),
+///         vertical: VerticalPrism::from_signed(0.25),
+///     },
+/// };
+/// let measured = MeasuredSpectacles { od: reading, os: reading, pd_mm: Some(63.0) };
+///
+/// let report = verify_spectacles(&ordered, &measured, ToleranceStandard::AnsiZ80_1);
+/// assert!(report.passed());
+/// ```
+pub fn verify_spectacles(
+    ordered: &SpectacleRx,
+    measured: &MeasuredSpectacles,
+    standard: ToleranceStandard,
+) -> VerificationReport {
+    let mut checks = Vec::new();
+    for (eye, ordered_eye, measured_eye) in [
+        (Eye::OD, &ordered.od, &measured.od),
+        (Eye::OS, &ordered.os, &measured.os),
+    ] {
+        checks.extend(lens_checks(
+            ordered_eye.lens,
+            measured_eye.lens,
+            Some(eye),
+            standard,
+        ));
+        if let (Some(expected), Some(read)) = (ordered_eye.add, measured_eye.add) {
+            checks.push(ToleranceCheck::new(
+                ToleranceItem::Add,
+                Some(eye),
+                expected,
+                read,
+                read - expected,
+                standard.add_tolerance(expected),
+            ));
+        }
+    }
+
+    let unwanted = |o: &EyeRx, m: &MeasuredLens| {
+        (
+            m.prism.horizontal.signed() - o.prism.horizontal.signed(),
+            m.prism.vertical.signed() - o.prism.vertical.signed(),
+        )
+    };
+    let (od_h, od_v) = unwanted(&ordered.od, &measured.od);
+    let (os_h, os_v) = unwanted(&ordered.os, &measured.os);
+    let mean_power = |phi: f64| mean_meridian_power(ordered.od.lens, ordered.os.lens, phi);
+
+    let horizontal = od_h + os_h;
+    checks.push(ToleranceCheck::new(
+        ToleranceItem::HorizontalPrismImbalance,
+        None,
+        0.0,
+        horizontal,
+        horizontal,
+        standard.horizontal_imbalance_tolerance(mean_power(0.0)),
+    ));
+    let vertical = od_v - os_v;
+    checks.push(ToleranceCheck::new(
+        ToleranceItem::VerticalPrismImbalance,
+        None,
+        0.0,
+        vertical,
+        vertical,
+        standard.vertical_imbalance_tolerance(mean_power(90.0)),
+    ));
+
+    if let (Some(expected), Some(read)) = (ordered.binocular_pd_mm(), measured.pd_mm) {
+        checks.push(ToleranceCheck::new(
+            ToleranceItem::Pd,
+            None,
+            expected,
+            read,
+            read - expected,
+            standard.pd_tolerance_mm(),
+        ));
+    }
+
+    VerificationReport { standard, checks }
+}
+
+/// Optical center position of one lens in the frame.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct OpticalCenterPosition {
+    /// Distance from the bridge center (mm).
+    ///
+    /// Example: 32 mm monocular PD → `monocular_pd_mm = 32.0`
+    pub monocular_pd_mm: f64,
+
+    /// Height above the lowest point of the box (mm).
+    ///
+    /// Example: OC 4 mm above the box center of a 40 mm B → `height_mm = 24.0`
+    pub height_mm: f64,
+}
+
+/// Optical center positions of both lenses.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct OpticalCenters {
+    /// Right lens.
+    pub od: OpticalCenterPosition,
+
+    /// Left lens.
+    pub os: OpticalCenterPosition,
+}
+
+impl OpticalCenters {
+    /// Returns the ordered positions, if the Rx has monocular PDs and OC heights for both eyes.
+    pub fn from_rx(rx: &SpectacleRx) -> Option<Self> {
+        let position = |eye: &EyeRx| {
+            Some(OpticalCenterPosition {
+                monocular_pd_mm: eye.monocular_pd_mm?,
+                height_mm: eye.oc_height_mm?,
+            })
+        };
+        Some(Self {
+            od: position(&rx.od)?,
+            os: position(&rx.os)?,
+        })
+    }
+}
+
+/// Unwanted prism from misplaced optical centers.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct PrismImbalance {
+    /// Prism induced in the right lens.
+    pub od: CombinedPrism,
+
+    /// Prism induced in the left lens.
+    pub os: CombinedPrism,
+
+    /// Net horizontal imbalance (Δ); positive = base out.
+    pub horizontal: f64,
+
+    /// Net vertical imbalance (Δ), OD minus OS; positive = base up before the right eye.
+    pub vertical: f64,
+
+    /// Horizontal check against the standard.
+    pub horizontal_check: ToleranceCheck,
+
+    /// Vertical check against the standard.
+    pub vertical_check: ToleranceCheck,
+}
+
+impl PrismImbalance {
+    /// Returns `true` if both checks pass.
+    pub fn passed(&self) -> bool {
+        self.horizontal_check.pass && self.vertical_check.pass
+    }
+}
+
+/// Check unwanted prism from the measured optical centers of a finished pair.
+///
+/// Each lens's decentration is the measured optical center minus the ordered
+/// one; the induced prism comes from [`induced_prism`].
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+///
+/// let lens = SpheroCyl { sphere: -5.00, cylinder: 0.0, axis_deg: 0.0 };
+/// let ordered = OpticalCenters {
+///     od: OpticalCenterPosition { monocular_pd_mm: 32.0, height_mm: 22.0 },
+///     os: OpticalCenterPosition { monocular_pd_mm: 32.0, height_mm: 22.0 },
+/// };
+/// // Left OC mounted 1.5 mm high
+/// let measured = OpticalCenters {
+///     os: OpticalCenterPosition { monocular_pd_mm: 32.0, height_mm: 23.5 },
+///     ..ordered
+/// };
+///
+/// let result = prism_imbalance(lens, lens, ordered, measured, ToleranceStandard::AnsiZ80_1);
+/// assert_abs_diff_eq!(result.vertical, 0.75, epsilon = 1e-9); // 0.75Δ BD before OS
+/// assert!(!result.passed());
+/// ```
+pub fn prism_imbalance(
+    od_lens: SpheroCyl,
+    os_lens: SpheroCyl,
+    ordered: OpticalCenters,
+    measured: OpticalCenters,
+    standard: ToleranceStandard,
+) -> PrismImbalance {
+    let induced =
+        |eye: Eye, lens: SpheroCyl, o: OpticalCenterPosition, m: OpticalCenterPosition| {
+            let dec = Decentration {
+                horizontal_mm: o.monocular_pd_mm - m.monocular_pd_mm,
+                vertical_mm: m.height_mm - o.height_mm,
+            };
+            induced_prism(eye, lens, dec)
+        };
+    let od = induced(Eye::OD, od_lens, ordered.od, measured.od);
+    let os = induced(Eye::OS, os_lens, ordered.os, measured.os);
+
+    let horizontal = od.horizontal.signed() + os.horizontal.signed();
+    let vertical = od.vertical.signed() - os.vertical.signed();
+
+    PrismImbalance {
+        od,
+        os,
+        horizontal,
+        vertical,
+        horizontal_check: ToleranceCheck::new(
+            ToleranceItem::HorizontalPrismImbalance,
+            None,
+            0.0,
+            horizontal,
+            horizontal,
+            standard.horizontal_imbalance_tolerance(mean_meridian_power(od_lens, os_lens, 0.0)),
+        ),
+        vertical_check: ToleranceCheck::new(
+            ToleranceItem::VerticalPrismImbalance,
+            None,
+            0.0,
+            vertical,
+            vertical,
+            standard.vertical_imbalance_tolerance(mean_meridian_power(od_lens, os_lens, 90.0)),
+        ),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    fn prism(horizontal: f64, vertical: f64) -> CombinedPrism {
+        CombinedPrism {
+            horizontal: HorizontalPrism::from_signed(horizontal),
+            vertical: VerticalPrism::from_signed(vertical),
+        }
+    }
+
+    fn check(report: &VerificationReport, item: ToleranceItem, eye: Option<Eye>) -> ToleranceCheck {
+        *report
+            .checks
+            .iter()
+            .find(|c| c.item == item && c.eye == eye)
+            .expect("check present")
+    }
+
+    #[test]
+    fn high_power_sphere_uses_percentage_under_ansi() {
+        let ordered = SpheroCyl {
+            sphere: -10.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let measured = SpheroCyl {
+            sphere: -10.18,
+            ..ordered
+        };
+        let report = verify_lens(ordered, measured, ToleranceStandard::AnsiZ80_1);
+        let sphere = check(&report, ToleranceItem::Sphere, None);
+        assert_abs_diff_eq!(sphere.tolerance, 0.2, epsilon = 1e-12);
+        assert!(sphere.pass);
+        // No cylinder: no axis check
+        assert_eq!(report.checks.len(), 2);
+
+        let iso = verify_lens(ordered, measured, ToleranceStandard::Iso21987);
+        assert!(iso.passed());
+        let iso = verify_lens(
+            ordered,
+            SpheroCyl {
+                sphere: -10.25,
+                ..ordered
+            },
+            ToleranceStandard::Iso21987,
+        );
+        assert!(!iso.passed());
+    }
+
+    #[test]
+    fn plus_cylinder_reading_is_transposed() {
+        // −2.00 −1.00 × 180 read as −3.00 +1.00 × 90
+        let ordered = SpheroCyl {
+            sphere: -2.0,
+            cylinder: -1.0,
+            axis_deg: 180.0,
+        };
+        let measured = SpheroCyl {
+            sphere: -3.0,
+            cylinder: 1.0,
+            axis_deg: 90.0,
+        };
+        let report = verify_lens(ordered, measured, ToleranceStandard::Iso21987);
+        assert!(report.passed());
+        let axis = check(&report, ToleranceItem::Axis, None);
+        assert_abs_diff_eq!(axis.deviation, 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn axis_deviation_wraps_across_180() {
+        let ordered = SpheroCyl {
+            sphere: 1.0,
+            cylinder: -2.0,
+            axis_deg: 178.0,
+        };
+        let measured = SpheroCyl {
+            axis_deg: 2.0,
+            ..ordered
+        };
+        let report = verify_lens(ordered, measured, ToleranceStandard::AnsiZ80_1);
+        let axis = check(&report, ToleranceItem::Axis, None);
+        assert_abs_diff_eq!(axis.deviation, 4.0, epsilon = 1e-9);
+        assert_eq!(axis.tolerance, 2.0);
+        assert!(!axis.pass);
+    }
+
+    #[test]
+    fn iso_cylinder_table_depends_on_meridian_power() {
+        let iso = ToleranceStandard::Iso21987;
+        assert_eq!(iso.cylinder_tolerance(-0.5, 2.0), 0.09);
+        assert_eq!(iso.cylinder_tolerance(-0.5, 10.0), 0.12);
+        assert_eq!(iso.cylinder_tolerance(-5.0, 10.0), 0.25);
+        assert_eq!(iso.cylinder_tolerance(-7.0, 25.0), 0.37);
+    }
+
+    #[test]
+    fn vertical_imbalance_and_pd_are_binocular() {
+        let lens = SpheroCyl {
+            sphere: -4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let ordered = SpectacleRx {
+            od: EyeRx {
+                add: Some(2.0),
+                monocular_pd_mm: Some(31.0),
+                ..EyeRx::from_lens(lens)
+            },
+            os: EyeRx {
+                add: Some(2.0),
+                monocular_pd_mm: Some(31.0),
+                ..EyeRx::from_lens(lens)
+            },
+        };
+        // OD 0.25Δ BU and OS 0.25Δ BD: 0.50Δ net vertical imbalance
+        let measured = MeasuredSpectacles {
+            od: MeasuredLens {
+                lens,
+                add: Some(2.12),
+                prism: prism(0.0, 0.25),
+            },
+            os: MeasuredLens {
+                lens,
+                add: Some(2.0),
+                prism: prism(0.0, -0.25),
+            },
+            pd_mm: Some(65.0),
+        };
+
+        let ansi = verify_spectacles(&ordered, &measured, ToleranceStandard::AnsiZ80_1);
+        let vertical = check(&ansi, ToleranceItem::VerticalPrismImbalance, None);
+        assert_abs_diff_eq!(vertical.measured, 0.5, epsilon = 1e-12);
+        assert_abs_diff_eq!(vertical.tolerance, 0.4, epsilon = 1e-12);
+        assert!(!vertical.pass);
+        assert!(check(&ansi, ToleranceItem::Add, Some(Eye::OD)).pass);
+        assert!(!check(&ansi, ToleranceItem::Pd, None).pass);
+        assert_eq!(ansi.failures().count(), 2);
+
+        let iso = verify_spectacles(&ordered, &measured, ToleranceStandard::Iso21987);
+        assert!(check(&iso, ToleranceItem::VerticalPrismImbalance, None).pass);
+    }
+
+    #[test]
+    fn prism_imbalance_from_measured_centers() {
+        // +4.00 OD and OS, both OCs 2 mm too wide: 0.8Δ BO each, 1.6Δ net
+        let lens = SpheroCyl {
+            sphere: 4.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let ordered = OpticalCenters {
+            od: OpticalCenterPosition {
+                monocular_pd_mm: 31.0,
+                height_mm: 20.0,
+            },
+            os: OpticalCenterPosition {
+                monocular_pd_mm: 31.0,
+                height_mm: 20.0,
+            },
+        };
+        let measured = OpticalCenters {
+            od: OpticalCenterPosition {
+                monocular_pd_mm: 33.0,
+                height_mm: 20.0,
+            },
+            os: OpticalCenterPosition {
+                monocular_pd_mm: 33.0,
+                height_mm: 20.0,
+            },
+        };
+
+        let result = prism_imbalance(lens, lens, ordered, measured, ToleranceStandard::Iso21987);
+        assert_eq!(result.od.horizontal.base(), Some(HorizontalBase::Out));
+        assert_eq!(result.os.horizontal.base(), Some(HorizontalBase::Out));
+        assert_abs_diff_eq!(result.horizontal, 1.6, epsilon = 1e-9);
+        assert_abs_diff_eq!(result.horizontal_check.tolerance, 1.0, epsilon = 1e-12);
+        assert!(!result.horizontal_check.pass);
+        assert!(result.vertical_check.pass);
+    }
+
+    #[test]
+    fn equal_vertical_shift_has_no_imbalance() {
+        let lens = SpheroCyl {
+            sphere: -6.0,
+            cylinder: -1.0,
+            axis_deg: 45.0,
+        };
+        let ordered = OpticalCenters {
+            od: OpticalCenterPosition {
+                monocular_pd_mm: 30.0,
+                height_mm: 22.0,
+            },
+            os: OpticalCenterPosition {
+                monocular_pd_mm: 30.0,
+                height_mm: 22.0,
+            },
+        };
+        let measured = OpticalCenters {
+            od: OpticalCenterPosition {
+                monocular_pd_mm: 30.0,
+                height_mm: 24.0,
+            },
+            os: OpticalCenterPosition {
+                monocular_pd_mm: 30.0,
+                height_mm: 24.0,
+            },
+        };
+        let result = prism_imbalance(lens, lens, ordered, measured, ToleranceStandard::AnsiZ80_1);
+        assert!(result.od.vertical.amount() > 1.0);
+        assert_abs_diff_eq!(result.vertical, 0.0, epsilon = 1e-9);
+    }
+
+    #[test]
+    fn ordered_centers_come_from_rx() {
+        let lens = SpheroCyl {
+            sphere: -1.0,
+            cylinder: 0.0,
+            axis_deg: 0.0,
+        };
+        let mut rx = SpectacleRx {
+            od: EyeRx {
+                monocular_pd_mm: Some(32.0),
+                oc_height_mm: Some(21.0),
+                ..EyeRx::from_lens(lens)
+            },
+            os: EyeRx {
+                monocular_pd_mm: Some(31.0),
+                oc_height_mm: Some(21.0),
+                ..EyeRx::from_lens(lens)
+            },
+        };
+        let centers = OpticalCenters::from_rx(&rx).unwrap();
+        assert_eq!(centers.os.monocular_pd_mm, 31.0);
+        rx.os.oc_height_mm = None;
+        assert!(OpticalCenters::from_rx(&rx).is_none());
+    }
+}
@@ -0,0 +1,415 @@
+//! Visual acuity notation conversions.
+//!
+//! ## Overview
+//! Stores an acuity as logMAR and converts to and from the notations used in
+//! clinical records: Snellen (feet or metres), decimal, MAR, M-units at a
+//! viewing distance and ETDRS letter scores.
+//!
+//! ## Formulas
+//! ```text
+//! MAR     = Snellen denominator / numerator = 1 / decimal = M / distance (m)
+//! logMAR  = log10(MAR)
+//! ETDRS   = 85 − 50 × logMAR   (letters read)
+//! ```
+//!
+//! ## Clinical Notes
+//! - Each letter on an ETDRS-style chart is worth 0.02 logMAR (five letters per
+//!   0.1 logMAR line), so "20/25-2" is two letters worse than 20/25.
+//! - Acuities must be averaged in logMAR space; averaging decimal or Snellen
+//!   fractions overweights the better acuities.
+
+use std::fmt;
+use std::str::FromStr;
+
+/// logMAR change per letter on an ETDRS-style chart.
+pub const LOGMAR_PER_LETTER: f64 = 0.02;
+
+/// A visual acuity, stored internally as logMAR.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct VisualAcuity {
+    /// Logarithm of the minimum angle of resolution.
+    /// 0.0 = 20/20, positive values are worse, negative values are better.
+    log_mar: f64,
+}
+
+/// An acuity expressed in every supported notation.
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "wasm", derive(tsify::Tsify))]
+#[cfg_attr(feature = "wasm", tsify(from_wasm_abi, into_wasm_abi))]
+pub struct AcuityNotations {
+    /// logMAR value.
+    pub log_mar: f64,
+    /// Decimal acuity (1.0 = 20/20).
+    pub decimal: f64,
+    /// Minimum angle of resolution in arc minutes.
+    pub mar: f64,
+    /// Snellen denominator over a 20 ft numerator.
+    pub snellen_feet: f64,
+    /// Snellen denominator over a 6 m numerator.
+    pub snellen_meters: f64,
+    /// ETDRS letter score (letters read).
+    pub etdrs_letters: f64,
+}
+
+impl VisualAcuity {
+    /// Creates an acuity from a logMAR value.
+    pub fn from_log_mar(log_mar: f64) -> Self {
+        Self { log_mar }
+    }
+
+    /// Creates an acuity from a minimum angle of resolution (arc minutes).
+    ///
+    /// # Panics
+    /// Panics if `mar` is not positive.
+    pub fn from_mar(mar: f64) -> Self {
+        assert!(mar > 0.0, "mar must be > 0.0");
+        Self::from_log_mar(mar.log10())
+    }
+
+    /// Creates an acuity from a decimal value (1.0 = 20/20).
+    ///
+    /// # Panics
+    /// Panics if `decimal` is not positive.
+    pub fn from_decimal(decimal: f64) -> Self {
+        assert!(decimal > 0.0, "decimal must be > 0.0");
+        Self::from_log_mar(-decimal.log10())
+    }
+
+    /// Creates an acuity from a Snellen fraction, in feet or metres.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// let va = VisualAcuity::from_snellen(20.0, 40.0);
+    /// assert_abs_diff_eq!(va.decimal(), 0.5, epsilon = 1e-12);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if either part is not positive.
+    pub fn from_snellen(numerator: f64, denominator: f64) -> Self {
+        assert!(numerator > 0.0, "numerator must be > 0.0");
+        assert!(denominator > 0.0, "denominator must be > 0.0");
+        Self::from_mar(denominator / numerator)
+    }
+
+    /// Creates an acuity from the smallest M-unit print read at a distance in metres.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    /// // 1.0M at 40 cm is equivalent to 20/50
+    /// let va = VisualAcuity::from_m_units(1.0, 0.4);
+    /// assert_abs_diff_eq!(va.snellen_denominator(20.0), 50.0, epsilon = 1e-9);
+    /// ```
+    ///
+    /// # Panics
+    /// Panics if either argument is not positive.
+    pub fn from_m_units(m_size: f64, distance_m: f64) -> Self {
+        assert!(m_size > 0.0, "m_size must be > 0.0");
+        assert!(distance_m > 0.0, "distance_m must be > 0.0");
+        Self::from_mar(m_size / distance_m)
+    }
+
+    /// Creates an acuity from an ETDRS letter score (letters read).
+    pub fn from_etdrs_letters(letters: f64) -> Self {
+        Self::from_log_mar((85.0 - letters) * LOGMAR_PER_LETTER)
+    }
+
+    /// Returns the acuity adjusted by a letter modifier.
+    ///
+    /// Positive `letters` are extra letters read on the next line (e.g. "+2"),
+    /// negative `letters` are letters missed on the recorded line (e.g. "-2").
+    pub fn with_letters(self, letters: i32) -> Self {
+        Self::from_log_mar(self.log_mar - letters as f64 * LOGMAR_PER_LETTER)
+    }
+
+    /// Returns the logMAR value.
+    pub fn log_mar(&self) -> f64 {
+        self.log_mar
+    }
+
+    /// Returns the minimum angle of resolution in arc minutes.
+    pub fn mar(&self) -> f64 {
+        10f64.powf(self.log_mar)
+    }
+
+    /// Returns the decimal acuity.
+    pub fn decimal(&self) -> f64 {
+        1.0 / self.mar()
+    }
+
+    /// Returns the Snellen denominator for a given numerator,
+    /// e.g. `20.0` for feet or `6.0` for metres.
+    pub fn snellen_denominator(&self, numerator: f64) -> f64 {
+        numerator * self.mar()
+    }
+
+    /// Returns the M-unit print size resolved at a distance in metres.
+    pub fn m_units_at(&self, distance_m: f64) -> f64 {
+        distance_m * self.mar()
+    }
+
+    /// Returns the ETDRS letter score (letters read).
+    pub fn etdrs_letters(&self) -> f64 {
+        85.0 - self.log_mar / LOGMAR_PER_LETTER
+    }
+
+    /// Returns the acuity in every supported notation.
+    pub fn notations(&self) -> AcuityNotations {
+        AcuityNotations {
+            log_mar: self.log_mar,
+            decimal: self.decimal(),
+            mar: self.mar(),
+            snellen_feet: self.snellen_denominator(20.0),
+            snellen_meters: self.snellen_denominator(6.0),
+            etdrs_letters: self.etdrs_letters(),
+        }
+    }
+}
+
+/// Average a set of acuities in logMAR space.
+///
+/// Returns `None` if `acuities` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use opticalc::*;
+/// use approx::assert_abs_diff_eq;
+/// // 20/20 and 20/200 average to logMAR 0.5 (≈ 20/63), not 20/110
+/// let a: VisualAcuity = "20/20".parse().unwrap();
+/// let b: VisualAcuity = "20/200".parse().unwrap();
+/// let mean = mean_visual_acuity(&[a, b]).unwrap();
+/// assert_abs_diff_eq!(mean.log_mar(), 0.5, epsilon = 1e-12);
+/// ```
+pub fn mean_visual_acuity(acuities: &[VisualAcuity]) -> Option<VisualAcuity> {
+    if acuities.is_empty() {
+        return None;
+    }
+    let sum: f64 = acuities.iter().map(|va| va.log_mar).sum();
+    Some(VisualAcuity::from_log_mar(sum / acuities.len() as f64))
+}
+
+/// Error returned when a visual acuity string cannot be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseVisualAcuityError {
+    input: String,
+}
+
+impl fmt::Display for ParseVisualAcuityError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid visual acuity notation: {:?}", self.input)
+    }
+}
+
+impl std::error::Error for ParseVisualAcuityError {}
+
+/// Parses a strictly positive number.
+fn positive(s: &str) -> Option<f64> {
+    s.parse::<f64>().ok().filter(|v| v.is_finite() && *v > 0.0)
+}
+
+/// Parses a distance with a `cm` or `m` unit into metres.
+fn distance_m(s: &str) -> Option<f64> {
+    if let Some(cm) = s.strip_suffix("cm") {
+        positive(cm).map(|v| v / 100.0)
+    } else {
+        s.strip_suffix('m').and_then(positive)
+    }
+}
+
+/// Parses a Snellen fraction with an optional letter modifier, e.g. `20/25-2`.
+fn parse_snellen(s: &str) -> Option<VisualAcuity> {
+    let (numerator, rest) = s.split_once('/')?;
+    let (denominator, letters) = match rest.find(['+', '-']) {
+        Some(i) => {
+            let letters: i32 = rest[i + 1..].parse().ok()?;
+            let letters = if rest.as_bytes()[i] == b'-' {
+                -letters
+            } else {
+                letters
+            };
+            (&rest[..i], letters)
+        }
+        None => (rest, 0),
+    };
+    Some(
+        VisualAcuity::from_snellen(positive(numerator)?, positive(denominator)?)
+            .with_letters(letters),
+    )
+}
+
+/// Parses M-unit notation: `1.0M@40cm`, `1.0M@0.4m` or Sloan's `0.4/1.0M`.
+fn parse_m_units(s: &str) -> Option<VisualAcuity> {
+    if let Some((size, distance)) = s.split_once('@') {
+        let size = positive(size.strip_suffix('m')?)?;
+        return Some(VisualAcuity::from_m_units(size, distance_m(distance)?));
+    }
+    let (distance, size) = s.split_once('/')?;
+    let size = positive(size.strip_suffix('m')?)?;
+    Some(VisualAcuity::from_m_units(size, positive(distance)?))
+}
+
+impl FromStr for VisualAcuity {
+    type Err = ParseVisualAcuityError;
+
+    /// Parses common acuity notations.
+    ///
+    /// Accepted forms (case-insensitive, whitespace ignored):
+    /// - Snellen: `20/40`, `6/12`, with letter modifiers such as `20/25-2` or `6/6+1`
+    /// - Decimal: `0.5`
+    /// - logMAR: `0.3 logMAR` or `logMAR 0.3`
+    /// - MAR: `MAR 2` or `2 MAR`
+    /// - M-units: `1.0M @ 40cm`, `1.0M at 0.4m` or `0.4/1.0M`
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use opticalc::*;
+    /// use approx::assert_abs_diff_eq;
+    ///
+    /// let va: VisualAcuity = "6/12".parse().unwrap();
+    /// assert_abs_diff_eq!(va.decimal(), 0.5, epsilon = 1e-12);
+    ///
+    /// let va: VisualAcuity = "0.3 logMAR".parse().unwrap();
+    /// assert_abs_diff_eq!(va.log_mar(), 0.3, epsilon = 1e-12);
+    ///
+    /// let va: VisualAcuity = "20/20-2".parse().unwrap();
+    /// assert_abs_diff_eq!(va.log_mar(), 0.04, epsilon = 1e-12);
+    ///
+    /// assert!("twenty/twenty".parse::<VisualAcuity>().is_err());
+    /// ```
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let normalized: String = s
+            .to_ascii_lowercase()
+            .replace(" at ", "@")
+            .chars()
+            .filter(|c| !c.is_whitespace())
+            .collect();
+        let err = || ParseVisualAcuityError {
+            input: s.to_string(),
+        };
+
+        let parsed = if let Some(v) = normalized
+            .strip_prefix("logmar")
+            .or_else(|| normalized.strip_suffix("logmar"))
+        {
+            v.parse::<f64>()
+                .ok()
+                .filter(|v| v.is_finite())
+                .map(VisualAcuity::from_log_mar)
+        } else if let Some(v) = normalized
+            .strip_prefix("mar")
+            .or_else(|| normalized.strip_suffix("mar"))
+        {
+            positive(v).map(VisualAcuity::from_mar)
+        } else if normalized.ends_with('m') || normalized.contains('@') {
+            parse_m_units(&normalized)
+        } else if normalized.contains('/') {
+            parse_snellen(&normalized)
+        } else {
+            positive(&normalized).map(VisualAcuity::from_decimal)
+        };
+
+        parsed.ok_or_else(err)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use approx::assert_abs_diff_eq;
+
+    const EPS: f64 = 1e-9;
+
+    fn parse(s: &str) -> VisualAcuity {
+        s.parse().unwrap()
+    }
+
+    #[test]
+    fn snellen_feet_and_metres_agree() {
+        assert_abs_diff_eq!(
+            parse("20/40").log_mar(),
+            parse("6/12").log_mar(),
+            epsilon = EPS
+        );
+        assert_abs_diff_eq!(parse("20/40").log_mar(), 2f64.log10(), epsilon = EPS);
+        assert_abs_diff_eq!(
+            parse("6/7.5").snellen_denominator(20.0),
+            25.0,
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn letter_modifiers_shift_by_two_hundredths() {
+        let base = parse("20/25").log_mar();
+        assert_abs_diff_eq!(parse("20/25-2").log_mar(), base + 0.04, epsilon = EPS);
+        assert_abs_diff_eq!(parse("20/25 +1").log_mar(), base - 0.02, epsilon = EPS);
+    }
+
+    #[test]
+    fn decimal_logmar_and_mar_notations() {
+        assert_abs_diff_eq!(parse("0.5").snellen_denominator(20.0), 40.0, epsilon = EPS);
+        assert_abs_diff_eq!(parse("logMAR 0.3").log_mar(), 0.3, epsilon = EPS);
+        assert_abs_diff_eq!(parse("-0.1 logMAR").log_mar(), -0.1, epsilon = EPS);
+        assert_abs_diff_eq!(parse("MAR 2").log_mar(), 2f64.log10(), epsilon = EPS);
+        assert_abs_diff_eq!(parse("1.25 MAR").mar(), 1.25, epsilon = EPS);
+    }
+
+    #[test]
+    fn m_units_at_distance() {
+        let a = parse("1.0M @ 40cm");
+        let b = parse("1.0 M at 0.4 m");
+        let c = parse("0.4/1.0M");
+        assert_abs_diff_eq!(a.mar(), 2.5, epsilon = EPS);
+        assert_abs_diff_eq!(a.log_mar(), b.log_mar(), epsilon = EPS);
+        assert_abs_diff_eq!(a.log_mar(), c.log_mar(), epsilon = EPS);
+        assert_abs_diff_eq!(a.m_units_at(0.4), 1.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn etdrs_letter_scores() {
+        assert_abs_diff_eq!(parse("20/20").etdrs_letters(), 85.0, epsilon = EPS);
+        assert_abs_diff_eq!(parse("20/200").etdrs_letters(), 35.0, epsilon = EPS);
+        assert_abs_diff_eq!(
+            VisualAcuity::from_etdrs_letters(70.0).log_mar(),
+            0.3,
+            epsilon = EPS
+        );
+    }
+
+    #[test]
+    fn notations_round_trip() {
+        let n = parse("20/63").notations();
+        assert_abs_diff_eq!(n.snellen_feet, 63.0, epsilon = EPS);
+        assert_abs_diff_eq!(n.snellen_meters, 18.9, epsilon = EPS);
+        assert_abs_diff_eq!(n.decimal, 20.0 / 63.0, epsilon = EPS);
+    }
+
+    #[test]
+    fn mean_is_computed_in_logmar_space() {
+        let mean = mean_visual_acuity(&[parse("20/20"), parse("20/40"), parse("20/80")]).unwrap();
+        assert_abs_diff_eq!(mean.snellen_denominator(20.0), 40.0, epsilon = EPS);
+        assert!(mean_visual_acuity(&[]).is_none());
+    }
+
+    #[test]
+    fn rejects_malformed_input() {
+        for s in [
+            "", "20/", "/20", "20/0", "abc", "0", "-0.5", "20/25-x", "1.0M@40", "logmar",
+        ] {
+            assert!(s.parse::<VisualAcuity>().is_err(), "{s:?} should not parse");
+        }
+        let err = "abc".parse::<VisualAcuity>().unwrap_err();
+        assert_eq!(err.to_string(), "invalid visual acuity notation: \"abc\"");
+    }
+}
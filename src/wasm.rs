@@ -1,9 +1,30 @@
 use wasm_bindgen::prelude::*;
 
 use crate::{
-    convert_power, convert_rx, crossed_cylinders, induced_prism, minimum_blank_size,
-    oblique_meridian, recommended_blank_size, simulate_lensmeter_reading, transpose, CombinedPrism,
-    Decentration, Eye, SpheroCyl,
+    alpins_analysis, alpins_cohort, assess_anisometropia, assess_chromatic_aberration,
+    assess_filter, bland_altman, compare_rx, compare_spectacle_rx, convert_power,
+    convert_power_wavelength, convert_rx, convert_rx_wavelength, crossed_cylinders,
+    decentration_for_prism, expected_corneal_astigmatism, fit_progressive, hofstetter_amplitude,
+    induced_prism, luminous_reflectance, luminous_transmittance, mean_visual_acuity,
+    minimum_blank_size, munnerlyn_ablation_depth, near_inset_mm, near_rx, normalize_axis_deg,
+    oblique_meridian, oblique_power_profile, per_eye_blank_size, plan_toric_iol, position_of_wear,
+    prism_imbalance, range_of_clear_vision, recommended_blank_size, reflectance_spectrum,
+    refraction_summary, reject_outliers, screen_refractive_surgery, simulate_lensmeter_reading,
+    surface_reflectance, tentative_add, trace_blank_size, transpose,
+    transverse_chromatic_aberration, uncoated_transmittance, verify_lens, verify_spectacles,
+    vertex_compensate, AblationScreening, AblationZone, AccommodationReserve, AcuityNotations,
+    AlpinsAnalysis, AlpinsCohort, AnisometropiaAssessment, AnisometropiaSetup, Astigmatism,
+    BlankCatalog, ChromaticAssessment, CombinedPrism, CornealTissue, CylinderForm, Decentration,
+    Dispersion, Eye, EyeCentration, FilterAssessment, FilterSpectrum, FrameMeasurements,
+    FrameTrace, HofstetterAmplitude, Keratometry, LensForm, LensMaterial, LensmeterReading,
+    LensmeterSetup, MeasuredSpectacles, NearViewing, ObliquePower, OpticalCenters,
+    OutlierScreening, PerEyeBlankSize, PositionOfWear, PositionOfWearRx, PowerVector,
+    PowerVectorAgreement, PrismImbalance, ProgressiveCentration, ProgressiveDesign, ProgressiveFit,
+    RangeOfClearVision, ReferenceWavelength, RefractionSummary, RoundedRx, RoundingOptions,
+    RxChange, RxChangeThresholds, SafetyThresholds, SpectacleRx, SpectacleRxChange,
+    SpectralReflectance, SpheroCyl, SpheroCylKey, StockBlank, SurgicallyInducedAstigmatism,
+    ThinFilmLayer, TiltModel, ToleranceStandard, ToricIolModel, ToricIolPlan, TraceBlankSize,
+    VerificationReport, VisualAcuity,
 };
 
 #[wasm_bindgen(js_name = convertPower)]
@@ -64,3 +85,437 @@ pub fn recommended_blank_size_wasm(
 pub fn induced_prism_wasm(eye: Eye, lens: SpheroCyl, dec: Decentration) -> CombinedPrism {
     induced_prism(eye, lens, dec)
 }
+
+#[wasm_bindgen(js_name = expectedCornealAstigmatism)]
+pub fn expected_corneal_astigmatism_wasm(
+    k: Keratometry,
+    sia: SurgicallyInducedAstigmatism,
+) -> Astigmatism {
+    expected_corneal_astigmatism(k, sia)
+}
+
+#[wasm_bindgen(js_name = planToricIol)]
+pub fn plan_toric_iol_wasm(
+    k: Keratometry,
+    sia: SurgicallyInducedAstigmatism,
+    models: Vec<ToricIolModel>,
+) -> Option<ToricIolPlan> {
+    plan_toric_iol(k, sia, &models)
+}
+
+#[wasm_bindgen(js_name = alpinsAnalysis)]
+pub fn alpins_analysis_wasm(
+    preop: Astigmatism,
+    target: Astigmatism,
+    postop: Astigmatism,
+) -> AlpinsAnalysis {
+    alpins_analysis(preop, target, postop)
+}
+
+#[wasm_bindgen(js_name = alpinsCohort)]
+pub fn alpins_cohort_wasm(cases: Vec<AlpinsAnalysis>) -> Option<AlpinsCohort> {
+    alpins_cohort(&cases)
+}
+
+#[wasm_bindgen(js_name = munnerlynAblationDepth)]
+pub fn munnerlyn_ablation_depth_wasm(correction: SpheroCyl, zone: AblationZone) -> f64 {
+    munnerlyn_ablation_depth(correction, zone)
+}
+
+#[wasm_bindgen(js_name = screenRefractiveSurgery)]
+pub fn screen_refractive_surgery_wasm(
+    correction: SpheroCyl,
+    zone: AblationZone,
+    tissue: CornealTissue,
+    thresholds: SafetyThresholds,
+) -> AblationScreening {
+    screen_refractive_surgery(correction, zone, tissue, thresholds)
+}
+
+#[wasm_bindgen(js_name = parseVisualAcuity)]
+pub fn parse_visual_acuity_wasm(notation: &str) -> Result<VisualAcuity, JsError> {
+    notation
+        .parse::<VisualAcuity>()
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = visualAcuityNotations)]
+pub fn visual_acuity_notations_wasm(acuity: VisualAcuity) -> AcuityNotations {
+    acuity.notations()
+}
+
+#[wasm_bindgen(js_name = meanVisualAcuity)]
+pub fn mean_visual_acuity_wasm(acuities: Vec<VisualAcuity>) -> Option<VisualAcuity> {
+    mean_visual_acuity(&acuities)
+}
+
+#[wasm_bindgen(js_name = hofstetterAmplitude)]
+pub fn hofstetter_amplitude_wasm(age_years: f64) -> HofstetterAmplitude {
+    hofstetter_amplitude(age_years)
+}
+
+#[wasm_bindgen(js_name = tentativeAdd)]
+pub fn tentative_add_wasm(
+    amplitude_d: f64,
+    working_distance_m: f64,
+    reserve: AccommodationReserve,
+) -> f64 {
+    tentative_add(amplitude_d, working_distance_m, reserve)
+}
+
+#[wasm_bindgen(js_name = rangeOfClearVision)]
+pub fn range_of_clear_vision_wasm(add_d: f64, amplitude_d: f64) -> RangeOfClearVision {
+    range_of_clear_vision(add_d, amplitude_d)
+}
+
+#[wasm_bindgen(js_name = nearRx)]
+pub fn near_rx_wasm(distance: SpheroCyl, add_d: f64) -> SpheroCyl {
+    near_rx(distance, add_d)
+}
+
+#[wasm_bindgen(js_name = decentrationForPrism)]
+pub fn decentration_for_prism_wasm(
+    eye: Eye,
+    lens: SpheroCyl,
+    prism: CombinedPrism,
+) -> Option<Decentration> {
+    decentration_for_prism(eye, lens, prism)
+}
+
+#[wasm_bindgen(js_name = perEyeBlankSize)]
+pub fn per_eye_blank_size_wasm(
+    frame: FrameMeasurements,
+    od: EyeCentration,
+    os: EyeCentration,
+    working_edge_mm: f64,
+) -> PerEyeBlankSize {
+    per_eye_blank_size(frame, od, os, working_edge_mm)
+}
+
+#[wasm_bindgen(js_name = traceBlankSize)]
+pub fn trace_blank_size_wasm(
+    eye: Eye,
+    radii_mm: Vec<f64>,
+    dec: Decentration,
+    working_edge_mm: f64,
+) -> Option<TraceBlankSize> {
+    trace_blank_size(eye, &radii_mm, dec, working_edge_mm)
+}
+
+#[wasm_bindgen(js_name = findBlanks)]
+pub fn find_blanks_wasm(
+    catalog: BlankCatalog,
+    rx: SpheroCyl,
+    material: LensMaterial,
+    base_curve: Option<f64>,
+    required_mm: f64,
+) -> Result<Vec<StockBlank>, JsError> {
+    catalog
+        .find_blanks(rx, material, base_curve, required_mm)
+        .map_err(|e| JsError::new(&e.to_string()))
+}
+
+#[wasm_bindgen(js_name = lensMaterialIndex)]
+pub fn lens_material_index_wasm(material: LensMaterial) -> f64 {
+    material.index()
+}
+
+#[wasm_bindgen(js_name = frameTraceFromRadii)]
+pub fn frame_trace_from_radii_wasm(eye: Eye, radii_mm: Vec<f64>) -> Option<FrameTrace> {
+    FrameTrace::from_radii(eye, &radii_mm)
+}
+
+#[wasm_bindgen(js_name = frameTraceMeasurements)]
+pub fn frame_trace_measurements_wasm(trace: FrameTrace, dbl_mm: f64) -> FrameMeasurements {
+    trace.to_frame_measurements(dbl_mm)
+}
+
+#[wasm_bindgen(js_name = frameTraceCircumference)]
+pub fn frame_trace_circumference_wasm(trace: FrameTrace) -> f64 {
+    trace.circumference_mm()
+}
+
+#[wasm_bindgen(js_name = resizeFrameTrace)]
+pub fn resize_frame_trace_wasm(trace: FrameTrace, circumference_mm: f64) -> FrameTrace {
+    trace.resized_to_circumference(circumference_mm)
+}
+
+#[wasm_bindgen(js_name = mirrorFrameTrace)]
+pub fn mirror_frame_trace_wasm(trace: FrameTrace) -> FrameTrace {
+    trace.mirrored()
+}
+
+#[wasm_bindgen(js_name = resampleFrameTrace)]
+pub fn resample_frame_trace_wasm(trace: FrameTrace, count: usize) -> Vec<f64> {
+    trace.equiangular_radii(count)
+}
+
+#[wasm_bindgen(js_name = frameTraceBlankSize)]
+pub fn frame_trace_blank_size_wasm(
+    trace: FrameTrace,
+    dec: Decentration,
+    working_edge_mm: f64,
+) -> TraceBlankSize {
+    trace.blank_size(dec, working_edge_mm)
+}
+
+#[wasm_bindgen(js_name = vertexCompensate)]
+pub fn vertex_compensate_wasm(rx: SpheroCyl, from_vertex_mm: f64, to_vertex_mm: f64) -> SpheroCyl {
+    vertex_compensate(rx, from_vertex_mm, to_vertex_mm)
+}
+
+#[wasm_bindgen(js_name = positionOfWear)]
+pub fn position_of_wear_wasm(
+    rx: SpheroCyl,
+    wear: PositionOfWear,
+    index: f64,
+    model: TiltModel,
+) -> PositionOfWearRx {
+    position_of_wear(rx, wear, index, model)
+}
+
+#[wasm_bindgen(js_name = obliquePowerProfile)]
+pub fn oblique_power_profile_wasm(
+    rx: SpheroCyl,
+    form: LensForm,
+    center_of_rotation_mm: f64,
+    gaze_meridian_deg: f64,
+    gaze_angles_deg: Vec<f64>,
+) -> Vec<ObliquePower> {
+    oblique_power_profile(
+        rx,
+        form,
+        center_of_rotation_mm,
+        gaze_meridian_deg,
+        &gaze_angles_deg,
+    )
+}
+
+#[wasm_bindgen(js_name = lensMaterialAbbeNumber)]
+pub fn lens_material_abbe_number_wasm(material: LensMaterial) -> f64 {
+    material.abbe_number()
+}
+
+#[wasm_bindgen(js_name = transverseChromaticAberration)]
+pub fn transverse_chromatic_aberration_wasm(
+    eye: Eye,
+    lens: SpheroCyl,
+    dec: Decentration,
+    abbe_number: f64,
+) -> f64 {
+    transverse_chromatic_aberration(eye, lens, dec, abbe_number)
+}
+
+#[wasm_bindgen(js_name = assessChromaticAberration)]
+pub fn assess_chromatic_aberration_wasm(
+    eye: Eye,
+    lens: SpheroCyl,
+    material: LensMaterial,
+    zone_radius_mm: f64,
+    threshold: f64,
+) -> ChromaticAssessment {
+    assess_chromatic_aberration(eye, lens, material, zone_radius_mm, threshold)
+}
+
+#[wasm_bindgen(js_name = lensMaterialDispersion)]
+pub fn lens_material_dispersion_wasm(material: LensMaterial) -> Dispersion {
+    material.dispersion()
+}
+
+#[wasm_bindgen(js_name = indexAtWavelength)]
+pub fn index_at_wavelength_wasm(material: Dispersion, wavelength_nm: f64) -> f64 {
+    material.index_at(wavelength_nm)
+}
+
+#[wasm_bindgen(js_name = convertPowerWavelength)]
+pub fn convert_power_wavelength_wasm(
+    power_diopters: f64,
+    material: Dispersion,
+    from: ReferenceWavelength,
+    to: ReferenceWavelength,
+) -> f64 {
+    convert_power_wavelength(power_diopters, material, from, to)
+}
+
+#[wasm_bindgen(js_name = convertRxWavelength)]
+pub fn convert_rx_wavelength_wasm(
+    rx: SpheroCyl,
+    material: Dispersion,
+    from: ReferenceWavelength,
+    to: ReferenceWavelength,
+) -> SpheroCyl {
+    convert_rx_wavelength(rx, material, from, to)
+}
+
+#[wasm_bindgen(js_name = surfaceReflectance)]
+pub fn surface_reflectance_wasm(index: f64) -> f64 {
+    surface_reflectance(index)
+}
+
+#[wasm_bindgen(js_name = uncoatedTransmittance)]
+pub fn uncoated_transmittance_wasm(index: f64) -> f64 {
+    uncoated_transmittance(index)
+}
+
+#[wasm_bindgen(js_name = reflectanceSpectrum)]
+pub fn reflectance_spectrum_wasm(
+    substrate_index: f64,
+    layers: Vec<ThinFilmLayer>,
+) -> Vec<SpectralReflectance> {
+    reflectance_spectrum(substrate_index, &layers)
+}
+
+#[wasm_bindgen(js_name = luminousReflectance)]
+pub fn luminous_reflectance_wasm(spectrum: Vec<SpectralReflectance>) -> Option<f64> {
+    luminous_reflectance(&spectrum)
+}
+
+#[wasm_bindgen(js_name = lensMaterialUvCutoff)]
+pub fn lens_material_uv_cutoff_wasm(material: LensMaterial) -> f64 {
+    material.uv_cutoff_nm()
+}
+
+#[wasm_bindgen(js_name = luminousTransmittance)]
+pub fn luminous_transmittance_wasm(spectrum: FilterSpectrum) -> Option<f64> {
+    luminous_transmittance(&spectrum)
+}
+
+#[wasm_bindgen(js_name = assessFilter)]
+pub fn assess_filter_wasm(
+    spectrum: FilterSpectrum,
+    material: Option<LensMaterial>,
+) -> Option<FilterAssessment> {
+    assess_filter(&spectrum, material)
+}
+
+#[wasm_bindgen(js_name = verifyLens)]
+pub fn verify_lens_wasm(
+    ordered: SpheroCyl,
+    measured: SpheroCyl,
+    standard: ToleranceStandard,
+) -> VerificationReport {
+    verify_lens(ordered, measured, standard)
+}
+
+#[wasm_bindgen(js_name = verifySpectacles)]
+pub fn verify_spectacles_wasm(
+    ordered: SpectacleRx,
+    measured: MeasuredSpectacles,
+    standard: ToleranceStandard,
+) -> VerificationReport {
+    verify_spectacles(&ordered, &measured, standard)
+}
+
+#[wasm_bindgen(js_name = prismImbalance)]
+pub fn prism_imbalance_wasm(
+    od_lens: SpheroCyl,
+    os_lens: SpheroCyl,
+    ordered: OpticalCenters,
+    measured: OpticalCenters,
+    standard: ToleranceStandard,
+) -> PrismImbalance {
+    prism_imbalance(od_lens, os_lens, ordered, measured, standard)
+}
+
+#[wasm_bindgen(js_name = simulateLensmeter)]
+pub fn simulate_lensmeter_wasm(
+    eye: Eye,
+    rx: SpheroCyl,
+    form: LensForm,
+    setup: LensmeterSetup,
+) -> LensmeterReading {
+    LensmeterReading::simulate(eye, rx, form, setup)
+}
+
+#[wasm_bindgen(js_name = powerVector)]
+pub fn power_vector_wasm(rx: SpheroCyl) -> PowerVector {
+    PowerVector::from(rx)
+}
+
+#[wasm_bindgen(js_name = powerVectorToSpheroCyl)]
+pub fn power_vector_to_sphero_cyl_wasm(vector: PowerVector) -> SpheroCyl {
+    vector.to_sphero_cyl()
+}
+
+#[wasm_bindgen(js_name = roundRx)]
+pub fn round_rx_wasm(rx: SpheroCyl, options: RoundingOptions) -> RoundedRx {
+    rx.rounded(options)
+}
+
+#[wasm_bindgen(js_name = normalizeAxis)]
+pub fn normalize_axis_wasm(axis_deg: f64) -> f64 {
+    normalize_axis_deg(axis_deg)
+}
+
+#[wasm_bindgen(js_name = normalizeRx)]
+pub fn normalize_rx_wasm(rx: SpheroCyl, form: CylinderForm) -> SpheroCyl {
+    rx.normalized(form)
+}
+
+#[wasm_bindgen(js_name = rxApproxEq)]
+pub fn rx_approx_eq_wasm(a: SpheroCyl, b: SpheroCyl, tolerance: f64) -> bool {
+    a.approx_eq(&b, tolerance)
+}
+
+#[wasm_bindgen(js_name = rxCanonicalKey)]
+pub fn rx_canonical_key_wasm(rx: SpheroCyl) -> SpheroCylKey {
+    rx.canonical_key()
+}
+
+#[wasm_bindgen(js_name = compareRx)]
+pub fn compare_rx_wasm(old: SpheroCyl, new: SpheroCyl, thresholds: RxChangeThresholds) -> RxChange {
+    compare_rx(old, new, thresholds)
+}
+
+#[wasm_bindgen(js_name = compareSpectacleRx)]
+pub fn compare_spectacle_rx_wasm(
+    old: SpectacleRx,
+    new: SpectacleRx,
+    thresholds: RxChangeThresholds,
+) -> SpectacleRxChange {
+    compare_spectacle_rx(&old, &new, thresholds)
+}
+
+#[wasm_bindgen(js_name = refractionSummary)]
+pub fn refraction_summary_wasm(readings: Vec<SpheroCyl>) -> Option<RefractionSummary> {
+    refraction_summary(&readings)
+}
+
+#[wasm_bindgen(js_name = blandAltman)]
+pub fn bland_altman_wasm(
+    first: Vec<SpheroCyl>,
+    second: Vec<SpheroCyl>,
+) -> Option<PowerVectorAgreement> {
+    bland_altman(&first, &second)
+}
+
+#[wasm_bindgen(js_name = rejectOutliers)]
+pub fn reject_outliers_wasm(readings: Vec<SpheroCyl>, cutoff: f64) -> OutlierScreening {
+    reject_outliers(&readings, cutoff)
+}
+
+#[wasm_bindgen(js_name = assessAnisometropia)]
+pub fn assess_anisometropia_wasm(
+    od: SpheroCyl,
+    os: SpheroCyl,
+    setup: AnisometropiaSetup,
+) -> AnisometropiaAssessment {
+    assess_anisometropia(od, os, setup)
+}
+
+#[wasm_bindgen(js_name = nearInset)]
+pub fn near_inset_wasm(monocular_pd_mm: f64, viewing: NearViewing) -> f64 {
+    near_inset_mm(monocular_pd_mm, viewing)
+}
+
+#[wasm_bindgen(js_name = fitProgressive)]
+pub fn fit_progressive_wasm(
+    frame: FrameMeasurements,
+    trace: Option<FrameTrace>,
+    design: ProgressiveDesign,
+    od: ProgressiveCentration,
+    os: ProgressiveCentration,
+    viewing: NearViewing,
+) -> ProgressiveFit {
+    fit_progressive(frame, trace.as_ref(), design, od, os, viewing)
+}